@@ -0,0 +1,130 @@
+use crate::timing::Allocations;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// The system allocator, counting the allocations and the bytes in use on each thread, so that
+/// what a part allocates can be told apart from what other threads do at the same time.
+pub struct CountingAllocator;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    /// Allocations, counting a reallocation as one since it may move the memory.
+    count: u64,
+    current_bytes: u64,
+    peak_bytes: u64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            count: 0,
+            current_bytes: 0,
+            peak_bytes: 0,
+        })
+    };
+}
+
+/// Updates the counters of this thread, unless it is being torn down.
+fn update(f: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut updated = counters.get();
+        f(&mut updated);
+        counters.set(updated);
+    });
+}
+
+fn allocated(size: usize) {
+    update(|counters| {
+        counters.count += 1;
+        counters.current_bytes += size as u64;
+        counters.peak_bytes = counters.peak_bytes.max(counters.current_bytes);
+    });
+}
+
+/// Memory may be freed on another thread than the one it was allocated on, so the bytes in use
+/// stop at 0 rather than wrap around.
+fn freed(size: usize) {
+    update(|counters| counters.current_bytes = counters.current_bytes.saturating_sub(size as u64));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: passed on from the caller.
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: passed on from the caller.
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: passed on from the caller.
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: passed on from the caller.
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` and counts the allocations it made on this thread, and the most bytes it had in use
+/// at once, including what it returns.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let mut before = COUNTERS.get();
+    before.peak_bytes = before.current_bytes;
+    COUNTERS.set(before);
+
+    let result = f();
+    let after = COUNTERS.get();
+    let allocations = Allocations {
+        count: after.count - before.count,
+        peak_bytes: after.peak_bytes - before.current_bytes,
+    };
+    (result, allocations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn test_count() {
+        let (_, allocations) = count(|| black_box(7) * 6);
+        assert_eq!(allocations, Allocations::default());
+
+        let (vec, allocations) = count(|| black_box(vec![0_u8; 1000]));
+        assert_eq!(allocations.count, 1);
+        assert_eq!(allocations.peak_bytes, 1000);
+        drop(vec);
+
+        let ((), allocations) = count(|| {
+            let mut vec = Vec::with_capacity(10);
+            vec.extend_from_slice(&[1_u64; 10]);
+            vec.push(11);
+            let small = black_box(vec![0_u8; 8]);
+            drop(vec);
+            drop(small);
+        });
+        assert_eq!(allocations.count, 3);
+        assert_eq!(allocations.peak_bytes, 168);
+    }
+}
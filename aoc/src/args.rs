@@ -0,0 +1,699 @@
+use crate::days::NUM_DAYS;
+use std::{ops::RangeInclusive, path::PathBuf};
+
+pub const USAGE: &str = "\
+Usage: aoc <DAYS> [PART] [--input PATH] [--input-dir DIR] [--parallel]
+           [--trace | --trace-json PATH]
+       aoc verify [DAYS] [--input-dir DIR]
+       aoc time [DAYS] [--input-dir DIR] [--json PATH] [--baseline PATH]
+       aoc visualize <DAY> [PART] [--format FORMAT] [--output DIR] [--input PATH]
+                     [--input-dir DIR]
+       aoc new <DAY>
+       aoc fetch [DAYS] [--input-dir DIR]
+       aoc submit <DAY> <PART> [ANSWER] [--input PATH] [--input-dir DIR]
+
+Commands:
+  verify  Run every input with known answers in `answers.toml` and print a
+          matrix of the parts that pass, fail or are missing
+  time    Time parsing and solving every part until the times are stable, and
+          print them as a Markdown table ranked from slowest to fastest. Built
+          with `--features count-allocations`, it also counts the allocations
+          and the peak memory of each part
+  visualize
+          Show how a day gets to its answer, as frames printed one after the
+          other or written to files. Days 5, 8, 9, 10 and 12 have frames
+  new     Add the crate of DAY, which must be the day after the last one, with
+          stubs to fill in, and register it with the runner, the fuzz targets
+          and answers.toml
+  fetch   Download the puzzle inputs that are not there yet, with the session
+          in $AOC_SESSION
+  submit  Submit the answer to a part, which is solved from the input unless
+          ANSWER is given, and add it to `answers.toml` if it is right
+
+Arguments:
+  <DAYS>  A day (`7`), a range of days (`3..9` or `3-9`) or `all` [default
+          for verify, time and fetch: all]
+  [PART]  `1`, `2` or `all` [default: all, and 1 for visualize]
+
+Options:
+  --input PATH     Read the puzzle input from PATH instead of `day_NN/input.txt`.
+                   Only allowed when running a single day.
+  --input-dir DIR  Read the puzzle inputs from `DIR/day_NN/input.txt`. Defaults to
+                   $AOC_INPUT_DIR, or the root of the workspace if it is not set.
+  --json PATH      Also write the times to PATH as JSON, to compare against later.
+  --baseline PATH  Compare the times to those in a JSON file written by --json,
+                   and list the parts that became more than 10% slower.
+  --parallel       Solve the parts on a thread pool, and compare the wall-clock
+                   time to the time the parts took together.
+  --trace          Print the events the solutions emit as they go, like the moves
+                   of day 5 or the ticks of day 10, before each answer. Needs aoc
+                   to be built with `--features trace`.
+  --trace-json PATH
+                   Write the events to PATH as JSON Lines instead, one object
+                   with `day`, `part`, `seq`, `event` and `fields` per line.
+  --format FORMAT  Draw frames as `ascii`, `ansi` (with colours) or `svg`
+                   [default: ansi]
+  --output DIR     Write every frame to its own file in DIR instead of printing
+                   them. Required for svg.
+  -h, --help       Print this help";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub days: RangeInclusive<u8>,
+    pub parts: RangeInclusive<u8>,
+    pub input: Option<PathBuf>,
+    pub input_dir: Option<PathBuf>,
+    pub parallel: bool,
+    pub trace: Option<Trace>,
+}
+
+/// Where the events the solutions emit go.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Trace {
+    Print,
+    Json(PathBuf),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyArgs {
+    pub days: RangeInclusive<u8>,
+    pub input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TimeArgs {
+    pub days: RangeInclusive<u8>,
+    pub input_dir: Option<PathBuf>,
+    pub json: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FetchArgs {
+    pub days: RangeInclusive<u8>,
+    pub input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub part: u8,
+    /// The answer to submit, instead of the one the solution finds.
+    pub answer: Option<String>,
+    pub input: Option<PathBuf>,
+    pub input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ascii,
+    Ansi,
+    Svg,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VisualizeArgs {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<PathBuf>,
+    pub input_dir: Option<PathBuf>,
+    pub format: Format,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Args),
+    Verify(VerifyArgs),
+    Time(TimeArgs),
+    Visualize(VisualizeArgs),
+    New(u8),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Help,
+}
+
+/// The options, which are checked against the command once the whole command line was read.
+#[derive(Default, PartialEq, Eq)]
+struct Options {
+    input: Option<String>,
+    input_dir: Option<String>,
+    json: Option<String>,
+    baseline: Option<String>,
+    parallel: bool,
+    format: Option<String>,
+    output: Option<String>,
+    trace: bool,
+    trace_json: Option<String>,
+}
+
+/// The commands, which take the place of the days as the first argument.
+const COMMANDS: [&str; 6] = ["verify", "time", "visualize", "new", "fetch", "submit"];
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut options = Options::default();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            return Ok(Command::Help);
+        } else if arg == "--input" {
+            options.input = Some(args.next().ok_or("`--input` requires a path")?);
+        } else if let Some(path) = arg.strip_prefix("--input=") {
+            options.input = Some(path.to_owned());
+        } else if arg == "--input-dir" {
+            options.input_dir = Some(args.next().ok_or("`--input-dir` requires a directory")?);
+        } else if let Some(dir) = arg.strip_prefix("--input-dir=") {
+            options.input_dir = Some(dir.to_owned());
+        } else if arg == "--json" {
+            options.json = Some(args.next().ok_or("`--json` requires a path")?);
+        } else if let Some(path) = arg.strip_prefix("--json=") {
+            options.json = Some(path.to_owned());
+        } else if arg == "--baseline" {
+            options.baseline = Some(args.next().ok_or("`--baseline` requires a path")?);
+        } else if let Some(path) = arg.strip_prefix("--baseline=") {
+            options.baseline = Some(path.to_owned());
+        } else if arg == "--format" {
+            options.format = Some(args.next().ok_or("`--format` requires a format")?);
+        } else if let Some(format) = arg.strip_prefix("--format=") {
+            options.format = Some(format.to_owned());
+        } else if arg == "--output" {
+            options.output = Some(args.next().ok_or("`--output` requires a directory")?);
+        } else if let Some(dir) = arg.strip_prefix("--output=") {
+            options.output = Some(dir.to_owned());
+        } else if arg == "--parallel" {
+            options.parallel = true;
+        } else if arg == "--trace" {
+            options.trace = true;
+        } else if arg == "--trace-json" {
+            options.trace_json = Some(args.next().ok_or("`--trace-json` requires a path")?);
+        } else if let Some(path) = arg.strip_prefix("--trace-json=") {
+            options.trace_json = Some(path.to_owned());
+        } else if arg.starts_with('-') {
+            return Err(format!("unknown option `{arg}`"));
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let mut positional = positional.into_iter().peekable();
+    let is_command = positional
+        .peek()
+        .is_some_and(|arg| COMMANDS.contains(&arg.as_str()));
+    if is_command && (options.trace || options.trace_json.is_some()) {
+        return Err(String::from(
+            "`--trace` and `--trace-json` can only be used when running days",
+        ));
+    }
+    if positional.next_if(|arg| arg == "visualize").is_some() {
+        return parse_visualize(positional, options).map(Command::Visualize);
+    }
+    if positional.next_if(|arg| arg == "new").is_some() {
+        return parse_new(positional, &options).map(Command::New);
+    }
+    if options.format.is_some() || options.output.is_some() {
+        return Err(String::from(
+            "`--format` and `--output` can only be used with visualize",
+        ));
+    }
+    if positional.next_if(|arg| arg == "submit").is_some() {
+        return parse_submit(positional, options).map(Command::Submit);
+    }
+    if let Some(command) =
+        positional.next_if(|arg| arg == "verify" || arg == "time" || arg == "fetch")
+    {
+        return parse_range_command(&command, positional, options);
+    }
+    parse_run(positional, options).map(Command::Run)
+}
+
+/// Parses running days, which is what the command line does when it does not start with a
+/// command.
+fn parse_run(
+    mut positional: impl Iterator<Item = String>,
+    options: Options,
+) -> Result<Args, String> {
+    let days = parse_days(&positional.next().ok_or("missing <DAYS> argument")?)?;
+    let parts = match positional.next() {
+        Some(part) => parse_parts(&part)?,
+        None => 1..=2,
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument `{extra}`"));
+    }
+    if options.json.is_some() || options.baseline.is_some() {
+        return Err(String::from(
+            "`--json` and `--baseline` can only be used with time",
+        ));
+    }
+    if options.input.is_some() && days.start() != days.end() {
+        return Err(String::from("`--input` can only be used with a single day"));
+    }
+    let trace = match (options.trace, options.trace_json) {
+        (false, None) => None,
+        (true, None) => Some(Trace::Print),
+        (false, Some(path)) => Some(Trace::Json(PathBuf::from(path))),
+        (true, Some(_)) => {
+            return Err(String::from(
+                "`--trace` and `--trace-json` cannot be used together",
+            ))
+        }
+    };
+    if trace.is_some() && options.parallel {
+        return Err(String::from(
+            "`--trace` and `--trace-json` cannot be used with `--parallel`",
+        ));
+    }
+
+    Ok(Args {
+        days,
+        parts,
+        input: options.input.map(PathBuf::from),
+        input_dir: options.input_dir.map(PathBuf::from),
+        parallel: options.parallel,
+        trace,
+    })
+}
+
+/// Parses the commands that work on a range of days, all of them by default.
+fn parse_range_command(
+    command: &str,
+    mut positional: impl Iterator<Item = String>,
+    options: Options,
+) -> Result<Command, String> {
+    let days = match positional.next() {
+        Some(days) => parse_days(&days)?,
+        None => 1..=NUM_DAYS,
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument `{extra}`"));
+    }
+    if options.input.is_some() {
+        return Err(format!("`--input` cannot be used with {command}"));
+    }
+    if options.parallel {
+        return Err(format!("`--parallel` cannot be used with {command}"));
+    }
+    let input_dir = options.input_dir.map(PathBuf::from);
+    if command == "time" {
+        return Ok(Command::Time(TimeArgs {
+            days,
+            input_dir,
+            json: options.json.map(PathBuf::from),
+            baseline: options.baseline.map(PathBuf::from),
+        }));
+    } else if options.json.is_some() || options.baseline.is_some() {
+        return Err(String::from(
+            "`--json` and `--baseline` can only be used with time",
+        ));
+    }
+    if command == "fetch" {
+        return Ok(Command::Fetch(FetchArgs { days, input_dir }));
+    }
+    Ok(Command::Verify(VerifyArgs { days, input_dir }))
+}
+
+fn parse_submit(
+    mut positional: impl Iterator<Item = String>,
+    options: Options,
+) -> Result<SubmitArgs, String> {
+    let day = parse_day(&positional.next().ok_or("missing <DAY> argument")?)?;
+    let part = match positional.next().as_deref() {
+        Some("1") => 1,
+        Some("2") => 2,
+        Some(part) => return Err(format!("invalid part `{part}`, expected 1 or 2")),
+        None => return Err(String::from("missing <PART> argument")),
+    };
+    let answer = positional.next();
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument `{extra}`"));
+    }
+    let other_options = [
+        &options.json,
+        &options.baseline,
+        &options.format,
+        &options.output,
+    ];
+    if options.parallel || other_options.iter().any(|option| option.is_some()) {
+        return Err(String::from(
+            "submit takes no options other than `--input` and `--input-dir`",
+        ));
+    }
+
+    Ok(SubmitArgs {
+        day,
+        part,
+        answer,
+        input: options.input.map(PathBuf::from),
+        input_dir: options.input_dir.map(PathBuf::from),
+    })
+}
+
+fn parse_new(
+    mut positional: impl Iterator<Item = String>,
+    options: &Options,
+) -> Result<u8, String> {
+    let day = positional.next().ok_or("missing <DAY> argument")?;
+    let Ok(day @ 1..=25) = day.parse() else {
+        return Err(format!("invalid day `{day}`, expected 1 to 25"));
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument `{extra}`"));
+    }
+    if *options != Options::default() {
+        return Err(String::from("new takes no options"));
+    }
+    Ok(day)
+}
+
+fn parse_visualize(
+    mut positional: impl Iterator<Item = String>,
+    options: Options,
+) -> Result<VisualizeArgs, String> {
+    let day = parse_day(&positional.next().ok_or("missing <DAY> argument")?)?;
+    let part = match positional.next().as_deref() {
+        Some("1") | None => 1,
+        Some("2") => 2,
+        Some(part) => return Err(format!("invalid part `{part}`, expected 1 or 2")),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument `{extra}`"));
+    }
+    if options.json.is_some() || options.baseline.is_some() {
+        return Err(String::from(
+            "`--json` and `--baseline` can only be used with time",
+        ));
+    }
+    if options.parallel {
+        return Err(String::from("`--parallel` cannot be used with visualize"));
+    }
+    let format = match options.format.as_deref() {
+        Some("ascii") => Format::Ascii,
+        Some("ansi") | None => Format::Ansi,
+        Some("svg") => Format::Svg,
+        Some(format) => {
+            return Err(format!(
+                "invalid format `{format}`, expected ascii, ansi or svg"
+            ))
+        }
+    };
+    if format == Format::Svg && options.output.is_none() {
+        return Err(String::from("`--format svg` requires `--output`"));
+    }
+
+    Ok(VisualizeArgs {
+        day,
+        part,
+        input: options.input.map(PathBuf::from),
+        input_dir: options.input_dir.map(PathBuf::from),
+        format,
+        output: options.output.map(PathBuf::from),
+    })
+}
+
+fn parse_days(arg: &str) -> Result<RangeInclusive<u8>, String> {
+    if arg == "all" {
+        return Ok(1..=NUM_DAYS);
+    }
+
+    let (start, end) = if let Some((start, end)) = arg.split_once("..=") {
+        (parse_day(start)?, parse_day(end)?)
+    } else if let Some((start, end)) = arg.split_once("..").or_else(|| arg.split_once('-')) {
+        (parse_day(start)?, parse_day(end)?)
+    } else {
+        let day = parse_day(arg)?;
+        (day, day)
+    };
+
+    if start > end {
+        Err(format!("empty range of days `{arg}`"))
+    } else {
+        Ok(start..=end)
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u8, String> {
+    match arg.parse() {
+        Ok(day @ 1..=NUM_DAYS) => Ok(day),
+        _ => Err(format!("invalid day `{arg}`, expected 1 to {NUM_DAYS}")),
+    }
+}
+
+fn parse_parts(arg: &str) -> Result<RangeInclusive<u8>, String> {
+    match arg {
+        "1" => Ok(1..=1),
+        "2" => Ok(2..=2),
+        "all" => Ok(1..=2),
+        _ => Err(format!("invalid part `{arg}`, expected 1, 2 or all")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|&arg| String::from(arg)))
+    }
+
+    #[test]
+    fn test_single_day() {
+        assert_eq!(
+            parse(&["5", "2"]),
+            Ok(Command::Run(Args {
+                days: 5..=5,
+                parts: 2..=2,
+                input: None,
+                input_dir: None,
+                parallel: false,
+                trace: None
+            }))
+        );
+    }
+
+    #[test]
+    fn test_ranges() {
+        for range in ["3..9", "3..=9", "3-9"] {
+            assert_eq!(parse_days(range), Ok(3..=9));
+        }
+        assert_eq!(parse_days("all"), Ok(1..=NUM_DAYS));
+        assert!(parse_days("9..3").is_err());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+    }
+
+    #[test]
+    fn test_input() {
+        assert_eq!(
+            parse(&["12", "--input", "other.txt"]),
+            Ok(Command::Run(Args {
+                days: 12..=12,
+                parts: 1..=2,
+                input: Some(PathBuf::from("other.txt")),
+                input_dir: None,
+                parallel: false,
+                trace: None
+            }))
+        );
+        assert_eq!(
+            parse(&["all", "--input-dir=inputs"]),
+            Ok(Command::Run(Args {
+                days: 1..=NUM_DAYS,
+                parts: 1..=2,
+                input: None,
+                input_dir: Some(PathBuf::from("inputs")),
+                parallel: false,
+                trace: None
+            }))
+        );
+        assert!(parse(&["1..3", "--input=other.txt"]).is_err());
+        assert!(parse(&["1", "--input"]).is_err());
+        assert!(parse(&["1", "--input-dir"]).is_err());
+    }
+
+    #[test]
+    fn test_parallel() {
+        assert_eq!(
+            parse(&["all", "2", "--parallel"]),
+            Ok(Command::Run(Args {
+                days: 1..=NUM_DAYS,
+                parts: 2..=2,
+                input: None,
+                input_dir: None,
+                parallel: true,
+                trace: None
+            }))
+        );
+        assert!(parse(&["verify", "--parallel"]).is_err());
+        assert!(parse(&["time", "--parallel"]).is_err());
+    }
+
+    #[test]
+    fn test_trace() {
+        assert_eq!(
+            parse(&["10", "1", "--trace"]),
+            Ok(Command::Run(Args {
+                days: 10..=10,
+                parts: 1..=1,
+                input: None,
+                input_dir: None,
+                parallel: false,
+                trace: Some(Trace::Print)
+            }))
+        );
+        assert_eq!(
+            parse(&["5..7", "--trace-json=trace.jsonl"]),
+            Ok(Command::Run(Args {
+                days: 5..=7,
+                parts: 1..=2,
+                input: None,
+                input_dir: None,
+                parallel: false,
+                trace: Some(Trace::Json(PathBuf::from("trace.jsonl")))
+            }))
+        );
+        assert!(parse(&["5", "--trace", "--trace-json", "trace.jsonl"]).is_err());
+        assert!(parse(&["5", "--trace", "--parallel"]).is_err());
+        assert!(parse(&["5", "--trace-json"]).is_err());
+        assert!(parse(&["verify", "--trace"]).is_err());
+        assert!(parse(&["visualize", "5", "--trace-json", "trace.jsonl"]).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(
+            parse(&["verify"]),
+            Ok(Command::Verify(VerifyArgs {
+                days: 1..=NUM_DAYS,
+                input_dir: None
+            }))
+        );
+        assert_eq!(
+            parse(&["verify", "3..5", "--input-dir", "inputs"]),
+            Ok(Command::Verify(VerifyArgs {
+                days: 3..=5,
+                input_dir: Some(PathBuf::from("inputs"))
+            }))
+        );
+        assert!(parse(&["verify", "1", "2"]).is_err());
+        assert!(parse(&["verify", "--input", "other.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_time() {
+        assert_eq!(
+            parse(&["time", "--json", "new.json", "--baseline=old.json"]),
+            Ok(Command::Time(TimeArgs {
+                days: 1..=NUM_DAYS,
+                input_dir: None,
+                json: Some(PathBuf::from("new.json")),
+                baseline: Some(PathBuf::from("old.json"))
+            }))
+        );
+        assert_eq!(
+            parse(&["time", "5", "--input-dir=inputs"]),
+            Ok(Command::Time(TimeArgs {
+                days: 5..=5,
+                input_dir: Some(PathBuf::from("inputs")),
+                json: None,
+                baseline: None
+            }))
+        );
+        assert!(parse(&["time", "--input", "other.txt"]).is_err());
+        assert!(parse(&["time", "--json"]).is_err());
+        assert!(parse(&["verify", "--json", "new.json"]).is_err());
+        assert!(parse(&["1", "--baseline", "old.json"]).is_err());
+    }
+
+    #[test]
+    fn test_visualize() {
+        assert_eq!(
+            parse(&["visualize", "9", "2", "--format=svg", "--output", "frames"]),
+            Ok(Command::Visualize(VisualizeArgs {
+                day: 9,
+                part: 2,
+                input: None,
+                input_dir: None,
+                format: Format::Svg,
+                output: Some(PathBuf::from("frames"))
+            }))
+        );
+        assert_eq!(
+            parse(&["visualize", "5", "--input", "other.txt"]),
+            Ok(Command::Visualize(VisualizeArgs {
+                day: 5,
+                part: 1,
+                input: Some(PathBuf::from("other.txt")),
+                input_dir: None,
+                format: Format::Ansi,
+                output: None
+            }))
+        );
+        assert!(parse(&["visualize"]).is_err());
+        assert!(parse(&["visualize", "1..3"]).is_err());
+        assert!(parse(&["visualize", "5", "all"]).is_err());
+        assert!(parse(&["visualize", "5", "--format", "png"]).is_err());
+        assert!(parse(&["visualize", "5", "--format", "svg"]).is_err());
+        assert!(parse(&["5", "--output", "frames"]).is_err());
+        assert!(parse(&["time", "--format=ascii"]).is_err());
+    }
+
+    #[test]
+    fn test_fetch() {
+        assert_eq!(
+            parse(&["fetch", "--input-dir", "inputs"]),
+            Ok(Command::Fetch(FetchArgs {
+                days: 1..=NUM_DAYS,
+                input_dir: Some(PathBuf::from("inputs"))
+            }))
+        );
+        assert!(parse(&["fetch", "3", "--input", "input.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        assert_eq!(
+            parse(&["submit", "7", "2"]),
+            Ok(Command::Submit(SubmitArgs {
+                day: 7,
+                part: 2,
+                answer: None,
+                input: None,
+                input_dir: None
+            }))
+        );
+        assert_eq!(
+            parse(&["submit", "10", "2", "EHPZPJGL", "--input-dir=inputs"]),
+            Ok(Command::Submit(SubmitArgs {
+                day: 10,
+                part: 2,
+                answer: Some(String::from("EHPZPJGL")),
+                input: None,
+                input_dir: Some(PathBuf::from("inputs"))
+            }))
+        );
+        assert!(parse(&["submit", "7"]).is_err());
+        assert!(parse(&["submit", "7", "all"]).is_err());
+        assert!(parse(&["submit", "7", "1", "42", "43"]).is_err());
+        assert!(parse(&["submit", "7", "1", "--parallel"]).is_err());
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(parse(&["new", "14"]), Ok(Command::New(14)));
+        assert!(parse(&["new"]).is_err());
+        assert!(parse(&["new", "26"]).is_err());
+        assert!(parse(&["new", "14", "15"]).is_err());
+        assert!(parse(&["new", "14", "--input-dir", "inputs"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["1", "3"]).is_err());
+        assert!(parse(&["1", "1", "1"]).is_err());
+        assert!(parse(&["1", "--verbose"]).is_err());
+    }
+}
@@ -0,0 +1,381 @@
+use common::input::input_path;
+use std::{
+    env,
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The environment variable with the session token: the value of the `session` cookie of a
+/// browser that is logged in to Advent of Code.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The puzzles of the year these are the solutions to.
+pub const BASE_URL: &str = "https://adventofcode.com/2022";
+
+/// Fetches puzzle inputs and submits answers on behalf of the user whose session it has.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+/// Where a fetched input is, and whether it had to be downloaded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input had been downloaded before, so it was not requested again.
+    Cached(PathBuf),
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// With the hint the site gives, like "your answer is too high", if it gives one.
+    Wrong(Option<String>),
+    /// With the time left to wait, like "1m 5s", if the site says.
+    RateLimited(Option<String>),
+    /// The part was solved already, or it is part 2 and part 1 was not solved yet.
+    WrongLevel,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("aoc/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Self {
+            agent,
+            base_url: base_url.into(),
+            session: session.into(),
+        }
+    }
+
+    /// A client for [`BASE_URL`] with the session in [`SESSION_VAR`].
+    pub fn from_env() -> Result<Self, ClientError> {
+        match env::var(SESSION_VAR) {
+            Ok(session) if !session.trim().is_empty() => Ok(Self::new(BASE_URL, session.trim())),
+            _ => Err(ClientError::MissingSession),
+        }
+    }
+
+    /// Downloads the input of a day to `day_NN/input.txt` below `dir`, unless it is there
+    /// already. Inputs never change, so there is no need to ask the site for them twice.
+    pub fn fetch_input(&self, dir: &Path, day: u8) -> Result<Fetched, ClientError> {
+        let path = input_path(dir, day);
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        let input = read_body(self.request("GET", day, "input").call())?;
+
+        let io_error = |source| ClientError::Io {
+            path: path.clone(),
+            source,
+        };
+        fs::create_dir_all(path.parent().unwrap()).map_err(io_error)?;
+        // Written next to the input and then renamed, so that an interrupted download is not
+        // mistaken for a cached input.
+        let partial = path.with_extension("txt.part");
+        fs::write(&partial, input).map_err(io_error)?;
+        fs::rename(&partial, &path).map_err(io_error)?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Submits the answer to one part of a day.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, ClientError> {
+        let request = self.request("POST", day, "answer");
+        let level = part.to_string();
+        let page = read_body(request.send_form(&[("level", &level), ("answer", answer)]))?;
+        parse_verdict(&page)
+    }
+
+    /// A request for a page of a day, with the session cookie.
+    fn request(&self, method: &str, day: u8, page: &str) -> ureq::Request {
+        let url = format!("{}/day/{day}/{page}", self.base_url.trim_end_matches('/'));
+        self.agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+fn read_body(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|error| ClientError::Transport(error.to_string())),
+        Err(ureq::Error::Status(status, response)) => Err(ClientError::Status {
+            url: response.get_url().to_owned(),
+            status,
+            body: response.into_string().unwrap_or_default(),
+        }),
+        Err(ureq::Error::Transport(error)) => Err(ClientError::Transport(error.to_string())),
+    }
+}
+
+/// Reads the verdict from the page the site answers a submission with.
+pub fn parse_verdict(page: &str) -> Result<Verdict, ClientError> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("That's not the right answer") {
+        let hint = ["too high", "too low"]
+            .into_iter()
+            .find(|hint| page.contains(&format!("your answer is {hint}")))
+            .map(|hint| format!("your answer is {hint}"));
+        Ok(Verdict::Wrong(hint))
+    } else if page.contains("You gave an answer too recently") {
+        let left = page
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(left, _)| left.to_owned());
+        Ok(Verdict::RateLimited(left))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        Err(ClientError::UnexpectedPage)
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    /// The site answered with an error status, such as 400 without a valid session or 404 for
+    /// a puzzle that is not unlocked yet.
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    /// The site could not be reached.
+    Transport(String),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A page without any of the messages a submission can get.
+    UnexpectedPage,
+}
+
+impl Display for ClientError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            Self::MissingSession => write!(
+                formatter,
+                "set {SESSION_VAR} to the value of the `session` cookie of a browser that is \
+                 logged in to Advent of Code"
+            ),
+            Self::Status { url, status, body } => {
+                write!(formatter, "{url} answered with status {status}")?;
+                match body.trim() {
+                    "" => Ok(()),
+                    body => write!(formatter, ": {body}"),
+                }
+            }
+            Self::Transport(error) => write!(formatter, "could not reach the site: {error}"),
+            Self::Io { path, source } => {
+                write!(formatter, "could not write {}: {source}", path.display())
+            }
+            Self::UnexpectedPage => {
+                write!(
+                    formatter,
+                    "could not find the verdict in the page the site sent"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// A request as the mock server received it.
+    #[derive(Debug)]
+    struct Request {
+        /// The request line, like `GET /day/1/input HTTP/1.1`.
+        line: String,
+        headers: Vec<String>,
+        body: String,
+    }
+
+    impl Request {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter().find_map(|header| {
+                let (key, value) = header.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then(|| value.trim())
+            })
+        }
+    }
+
+    /// Serves the responses, one per connection, on a local port, and returns its URL and a
+    /// handle that gives the requests it received once all responses are sent.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut lines = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end().to_owned();
+                    if line.is_empty() {
+                        break;
+                    }
+                    lines.push(line);
+                }
+                let mut request = Request {
+                    line: lines.remove(0),
+                    headers: lines,
+                    body: String::new(),
+                };
+                let length = request
+                    .header("Content-Length")
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                request.body = String::from_utf8(request_body).unwrap();
+                requests.push(request);
+
+                write!(
+                    &stream,
+                    "HTTP/1.1 {status} Mock\r\nContent-Type: text/html\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let client = Client::new(url, "abc123");
+        let dir = temp_dir("fetch");
+
+        let path = dir.join("day_01/input.txt");
+        assert_eq!(
+            client.fetch_input(&dir, 1).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert_eq!(client.fetch_input(&dir, 1).unwrap(), Fetched::Cached(path));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].line, "GET /day/1/input HTTP/1.1");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (url, server) = serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (404, ""),
+        ]);
+        let client = Client::new(url, "expired");
+        let dir = temp_dir("fetch-errors");
+
+        let error = client.fetch_input(&dir, 2).unwrap_err();
+        assert!(matches!(error, ClientError::Status { status: 400, .. }));
+        assert!(error.to_string().ends_with(
+            "/day/2/input answered with status 400: Puzzle inputs differ by user.  Please log in \
+             to get your puzzle input."
+        ));
+        let error = client.fetch_input(&dir, 25).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("/day/25/input answered with status 404"));
+        assert!(!dir.join("day_02/input.txt").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve(vec![
+            (200, "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>"),
+            (200, "<article><p>That's not the right answer; your answer is too low.  Please wait one minute.</p></article>"),
+            (200, "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait.</p></article>"),
+            (200, "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"),
+        ]);
+        let client = Client::new(url + "/", "abc123");
+        assert_eq!(client.submit(1, 1, "24000").unwrap(), Verdict::Correct);
+        assert_eq!(
+            client.submit(1, 2, "4500").unwrap(),
+            Verdict::Wrong(Some(String::from("your answer is too low")))
+        );
+        assert_eq!(
+            client.submit(1, 2, "4501").unwrap(),
+            Verdict::RateLimited(Some(String::from("37s")))
+        );
+        assert_eq!(client.submit(1, 1, "24000").unwrap(), Verdict::WrongLevel);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "POST /day/1/answer HTTP/1.1");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+        assert_eq!(
+            requests[0].header("Content-Type"),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(requests[0].body, "level=1&answer=24000");
+        assert_eq!(requests[1].body, "level=2&answer=4500");
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict("That's not the right answer.  If you're stuck, make sure...").unwrap(),
+            Verdict::Wrong(None)
+        );
+        assert_eq!(
+            parse_verdict("You gave an answer too recently.").unwrap(),
+            Verdict::RateLimited(None)
+        );
+        assert!(matches!(
+            parse_verdict("<html>Maintenance</html>"),
+            Err(ClientError::UnexpectedPage)
+        ));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let error = Client::new(url, "abc123").submit(1, 1, "1").unwrap_err();
+        assert!(matches!(error, ClientError::Transport(_)));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::timing::{time_day, DayTimings, Settings};
+#[cfg(feature = "visualize")]
+use common::visualize::{Frame, Visualize};
+use common::{Answer, ParseError, Solution};
+
+pub const NUM_DAYS: u8 = 13;
+
+type Solver = fn(u8, &str) -> Result<Answer, ParseError>;
+
+const SOLVERS: [Solver; NUM_DAYS as usize] = [
+    day_01::Day01::try_solve,
+    day_02::Day02::try_solve,
+    day_03::Day03::try_solve,
+    day_04::Day04::try_solve,
+    day_05::Day05::try_solve,
+    day_06::Day06::try_solve,
+    day_07::Day07::try_solve,
+    day_08::Day08::try_solve,
+    day_09::Day09::try_solve,
+    day_10::Day10::try_solve,
+    day_11::Day11::try_solve,
+    day_12::Day12::try_solve,
+    day_13::Day13::try_solve,
+];
+
+type Timer = fn(&str, &Settings) -> Result<DayTimings, ParseError>;
+
+const TIMERS: [Timer; NUM_DAYS as usize] = [
+    time_day::<day_01::Day01>,
+    time_day::<day_02::Day02>,
+    time_day::<day_03::Day03>,
+    time_day::<day_04::Day04>,
+    time_day::<day_05::Day05>,
+    time_day::<day_06::Day06>,
+    time_day::<day_07::Day07>,
+    time_day::<day_08::Day08>,
+    time_day::<day_09::Day09>,
+    time_day::<day_10::Day10>,
+    time_day::<day_11::Day11>,
+    time_day::<day_12::Day12>,
+    time_day::<day_13::Day13>,
+];
+
+#[cfg(feature = "visualize")]
+type Visualizer = fn(u8, &str) -> Result<Vec<Frame>, ParseError>;
+
+#[cfg(feature = "visualize")]
+const VISUALIZERS: [Option<Visualizer>; NUM_DAYS as usize] = [
+    None,
+    None,
+    None,
+    None,
+    Some(day_05::Day05::frames),
+    None,
+    None,
+    Some(day_08::Day08::frames),
+    Some(day_09::Day09::frames),
+    Some(day_10::Day10::frames),
+    None,
+    Some(day_12::Day12::frames),
+    None,
+];
+
+/// Solves one part of a day. Both `day` and `part` are 1-based and must have been validated.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer, ParseError> {
+    SOLVERS[usize::from(day - 1)](part, input)
+}
+
+/// Times parsing and solving both parts of a day, which is 1-based and must have been validated.
+pub fn time(day: u8, input: &str, settings: &Settings) -> Result<DayTimings, ParseError> {
+    TIMERS[usize::from(day - 1)](input, settings)
+}
+
+/// The frames of one part of a day, or `None` if the day has none. Both `day` and `part` are
+/// 1-based and must have been validated.
+#[cfg(feature = "visualize")]
+pub fn frames(day: u8, part: u8, input: &str) -> Option<Result<Vec<Frame>, ParseError>> {
+    VISUALIZERS[usize::from(day - 1)].map(|frames| frames(part, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_sample() {
+        let sample_input = include_str!("../../day_02/sample-input.txt");
+        assert_eq!(solve(2, 1, sample_input), Ok(Answer::Integer(15)));
+        assert_eq!(solve(2, 2, sample_input), Ok(Answer::Integer(12)));
+
+        let sample_input = include_str!("../../day_05/sample-input.txt");
+        assert_eq!(
+            solve(5, 1, sample_input),
+            Ok(Answer::Text(String::from("CMZ")))
+        );
+    }
+
+    #[test]
+    fn test_solve_invalid_input() {
+        let error = solve(2, 1, "A Y\nB W\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    #[cfg(feature = "visualize")]
+    fn test_frames() {
+        let sample_input = include_str!("../../day_05/sample-input.txt");
+        let day_05 = frames(5, 1, sample_input).unwrap().unwrap();
+        assert_eq!(day_05.len(), 5);
+        assert!(frames(1, 1, "1\n").is_none());
+    }
+}
@@ -0,0 +1,439 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+#[cfg(feature = "count-allocations")]
+mod alloc;
+mod args;
+mod client;
+mod days;
+mod parallel;
+mod scaffold;
+mod timing;
+#[cfg(feature = "trace")]
+mod trace;
+mod verify;
+
+use args::{
+    parse_args, Args, Command, FetchArgs, SubmitArgs, TimeArgs, Trace, VerifyArgs, VisualizeArgs,
+    USAGE,
+};
+use client::{Client, Fetched, Verdict};
+use common::{
+    input::{self, InputError},
+    manifest::Manifest,
+    Answer, ParseError,
+};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use timing::{Report, Settings};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+fn main() -> ExitCode {
+    match parse_args(env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run(args)) => run(&args),
+        Ok(Command::Verify(args)) => run_verify(args),
+        Ok(Command::Time(args)) => run_time(args),
+        Ok(Command::Visualize(args)) => run_visualize(&args),
+        Ok(Command::New(day)) => run_new(day),
+        Ok(Command::Fetch(args)) => run_fetch(args),
+        Ok(Command::Submit(args)) => run_submit(&args),
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> ExitCode {
+    if args.parallel {
+        return run_parallel(args);
+    }
+    if let Some(trace) = &args.trace {
+        return run_traced(args, trace);
+    }
+    run_with(args, days::solve)
+}
+
+/// Solves the parts one after the other with `solve`, and prints their answers.
+fn run_with(args: &Args, solve: impl Fn(u8, u8, &str) -> Result<Answer, ParseError>) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in args.days.clone() {
+        let input = match load_input(day, args.input.as_ref(), args.input_dir.as_ref()) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Day {day:02}: {error}");
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        for part in args.parts.clone() {
+            match solve(day, part, &input) {
+                Ok(answer) => print_answer(day, part, &answer),
+                Err(error) => {
+                    eprintln!("Day {day:02}, part {part}: invalid input at {error}");
+                    exit_code = ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    exit_code
+}
+
+#[cfg(feature = "trace")]
+fn run_traced(args: &Args, trace: &Trace) -> ExitCode {
+    let tracer = match trace::Tracer::create(trace) {
+        Ok(tracer) => tracer,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let exit_code = run_with(args, |day, part, input| tracer.solve(day, part, input));
+    if let Err(error) = tracer.finish() {
+        eprintln!("error: could not write the trace: {error}");
+        return ExitCode::FAILURE;
+    }
+    exit_code
+}
+
+#[cfg(not(feature = "trace"))]
+fn run_traced(_args: &Args, _trace: &Trace) -> ExitCode {
+    eprintln!("error: aoc was built without the `trace` feature");
+    ExitCode::FAILURE
+}
+
+fn run_parallel(args: &Args) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut inputs = Vec::new();
+
+    for day in args.days.clone() {
+        match load_input(day, args.input.as_ref(), args.input_dir.as_ref()) {
+            Ok(input) => inputs.push((day, input)),
+            Err(error) => {
+                eprintln!("Day {day:02}: {error}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let (solved, wall_clock) = parallel::solve_all(&inputs, args.parts.clone());
+    for solved in &solved {
+        match &solved.answer {
+            Ok(answer) => print_answer(solved.day, solved.part, answer),
+            Err(error) => {
+                eprintln!(
+                    "Day {:02}, part {}: invalid input at {error}",
+                    solved.day, solved.part
+                );
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    println!("\n{}", parallel::summary(&solved, wall_clock));
+
+    exit_code
+}
+
+fn run_verify(args: VerifyArgs) -> ExitCode {
+    let manifest = match Manifest::load(&Manifest::path()) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input_dir = args.input_dir.unwrap_or_else(input::input_dir);
+    let outcomes = verify::verify(&manifest, args.days, &input_dir);
+    println!("{}", verify::report(&outcomes));
+
+    let failed = outcomes
+        .iter()
+        .flat_map(|outcome| &outcome.parts)
+        .any(|status| matches!(status, verify::Status::Fail(_)));
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_time(args: TimeArgs) -> ExitCode {
+    let baseline = match args.baseline.as_deref().map(load_report).transpose() {
+        Ok(baseline) => baseline,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input_dir = args.input_dir.unwrap_or_else(input::input_dir);
+    let settings = Settings::default();
+    let mut report = Report::default();
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in args.days {
+        let input = match input::load_input_from(&input_dir, day) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Day {day:02}: {error}");
+                continue;
+            }
+        };
+        match days::time(day, &input, &settings) {
+            Ok(timings) => report.add_day(day, &timings),
+            Err(error) => {
+                eprintln!("Day {day:02}: invalid input at {error}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if report.parts.is_empty() {
+        eprintln!("error: no inputs to time");
+        return ExitCode::FAILURE;
+    }
+    println!("{}", report.table(baseline.as_ref()));
+
+    if let Some(path) = args.json {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        if let Err(error) = fs::write(&path, json + "\n") {
+            eprintln!("error: could not write {}: {error}", path.display());
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+fn run_new(day: u8) -> ExitCode {
+    let workspace = input::workspace_dir();
+    let changes = match scaffold::new_day(workspace, day) {
+        Ok(changes) => changes,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(message) = scaffold::apply(&changes) {
+        eprintln!("error: {message}");
+        return ExitCode::FAILURE;
+    }
+
+    for change in &changes {
+        let path = change.path.strip_prefix(workspace).unwrap_or(&change.path);
+        let action = if change.created { "Created" } else { "Changed" };
+        println!("{action} {}", path.display());
+    }
+    println!(
+        "\nPaste the example into day_{day:02}/sample-input.txt, fill in the answers in \
+         answers.toml as you find them, and run the tests with `cargo test -p day_{day:02}`."
+    );
+    ExitCode::SUCCESS
+}
+
+fn run_fetch(args: FetchArgs) -> ExitCode {
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input_dir = args.input_dir.unwrap_or_else(input::input_dir);
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in args.days {
+        match client.fetch_input(&input_dir, day) {
+            Ok(Fetched::Downloaded(path)) => println!("Day {day:02}: saved {}", path.display()),
+            Ok(Fetched::Cached(path)) => println!("Day {day:02}: {} is there", path.display()),
+            Err(error) => {
+                eprintln!("Day {day:02}: {error}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+fn run_submit(args: &SubmitArgs) -> ExitCode {
+    let (day, part) = (args.day, args.part);
+    let answer = match answer_to_submit(args) {
+        Ok(answer) => answer,
+        Err(message) => {
+            eprintln!("Day {day:02}, part {part}: {message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let manifest_path = Manifest::path();
+    let manifest = match Manifest::load(&manifest_path) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // Drawn answers are known as drawings, which cannot be compared to the letters submitted.
+    let known = manifest.answer(day, part, input::DEFAULT_INPUT);
+    match known.filter(|known| !known.is_multiline()) {
+        Some(known) if *known == answer => {
+            println!("Day {day:02}, part {part}: {answer} is the known answer, so it is right");
+            return ExitCode::SUCCESS;
+        }
+        Some(known) => {
+            eprintln!(
+                "Day {day:02}, part {part}: not submitting {answer}, because `answers.toml` has \
+                 {known} as the right answer"
+            );
+            return ExitCode::FAILURE;
+        }
+        None => (),
+    }
+    let verdict =
+        Client::from_env().and_then(|client| client.submit(day, part, &answer.to_string()));
+
+    let message = match verdict {
+        Ok(Verdict::Correct) => {
+            println!("Day {day:02}, part {part}: {answer} is the right answer");
+            if known.is_some() {
+                return ExitCode::SUCCESS;
+            }
+            match Manifest::record(&manifest_path, day, part, input::DEFAULT_INPUT, &answer) {
+                Ok(()) => {
+                    println!("Added it to {}", manifest_path.display());
+                    return ExitCode::SUCCESS;
+                }
+                Err(error) => error.to_string(),
+            }
+        }
+        Ok(Verdict::Wrong(hint)) => {
+            let hint = hint.map(|hint| format!("; {hint}")).unwrap_or_default();
+            format!("{answer} is not the right answer{hint}")
+        }
+        Ok(Verdict::RateLimited(left)) => {
+            let left = left.map(|left| format!(" in {left}")).unwrap_or_default();
+            format!("an answer was submitted too recently, try again{left}")
+        }
+        Ok(Verdict::WrongLevel) => {
+            String::from("this part cannot be submitted; it was solved already, or part 1 was not")
+        }
+        Err(error) => error.to_string(),
+    };
+    eprintln!("Day {day:02}, part {part}: {message}");
+    ExitCode::FAILURE
+}
+
+/// The answer given on the command line, or else the one the solution finds in the input.
+fn answer_to_submit(args: &SubmitArgs) -> Result<Answer, String> {
+    if let Some(answer) = &args.answer {
+        return Ok(answer
+            .parse()
+            .map_or_else(|_| Answer::Text(answer.clone()), Answer::Integer));
+    }
+    let input = load_input(args.day, args.input.as_ref(), args.input_dir.as_ref())
+        .map_err(|error| error.to_string())?;
+    let answer = days::solve(args.day, args.part, &input)
+        .map_err(|error| format!("invalid input at {error}"))?;
+    if answer.is_multiline() {
+        return Err(format!(
+            "the answer is drawn, so read it and pass it as ANSWER:\n{answer}"
+        ));
+    }
+    Ok(answer)
+}
+
+fn load_report(path: &Path) -> Result<Report, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|error| format!("could not read {}: {error}", path.display()))?;
+    serde_json::from_str(&json)
+        .map_err(|error| format!("invalid timing report {}: {error}", path.display()))
+}
+
+#[cfg(feature = "visualize")]
+fn run_visualize(args: &VisualizeArgs) -> ExitCode {
+    use common::visualize::{Ansi, Ascii, Renderer, Svg};
+
+    let (day, part) = (args.day, args.part);
+    let input = match load_input(day, args.input.as_ref(), args.input_dir.as_ref()) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Day {day:02}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let frames = match days::frames(day, part, &input) {
+        Some(Ok(frames)) => frames,
+        Some(Err(error)) => {
+            eprintln!("Day {day:02}, part {part}: invalid input at {error}");
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("error: day {day:02} has no visualization");
+            return ExitCode::FAILURE;
+        }
+    };
+    let renderer: Box<dyn Renderer> = match args.format {
+        args::Format::Ascii => Box::new(Ascii),
+        args::Format::Ansi => Box::new(Ansi),
+        args::Format::Svg => Box::new(Svg::default()),
+    };
+
+    let Some(dir) = &args.output else {
+        let frames: Vec<_> = frames.iter().map(|frame| renderer.render(frame)).collect();
+        print!("{}", frames.join("\n"));
+        return ExitCode::SUCCESS;
+    };
+    if let Err(error) = fs::create_dir_all(dir) {
+        eprintln!("error: could not create {}: {error}", dir.display());
+        return ExitCode::FAILURE;
+    }
+    for (index, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!(
+            "day_{day:02}-part_{part}-{:04}.{}",
+            index + 1,
+            renderer.extension()
+        ));
+        if let Err(error) = fs::write(&path, renderer.render(frame)) {
+            eprintln!("error: could not write {}: {error}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    println!("Wrote {} frames to {}", frames.len(), dir.display());
+    ExitCode::SUCCESS
+}
+
+#[cfg(not(feature = "visualize"))]
+fn run_visualize(_args: &VisualizeArgs) -> ExitCode {
+    eprintln!("error: aoc was built without the `visualize` feature");
+    ExitCode::FAILURE
+}
+
+fn load_input(
+    day: u8,
+    input: Option<&PathBuf>,
+    input_dir: Option<&PathBuf>,
+) -> Result<String, InputError> {
+    match (input, input_dir) {
+        (Some(path), _) => input::read_input(day, path.clone()),
+        (None, Some(dir)) => input::load_input_from(dir, day),
+        (None, None) => input::load_input(day),
+    }
+}
+
+fn print_answer(day: u8, part: u8, answer: &Answer) {
+    if answer.is_multiline() {
+        println!("Day {day:02}, part {part}:\n{answer}");
+    } else {
+        println!("Day {day:02}, part {part}: {answer}");
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{days, timing::format_nanos};
+use common::{Answer, ParseError};
+use rayon::prelude::*;
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+/// One part of a day, solved on the thread pool.
+#[derive(Debug)]
+pub struct Solved {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, ParseError>,
+    /// The time it took to parse the input and solve the part, on whichever thread it ran.
+    pub elapsed: Duration,
+}
+
+/// Solves `parts` of each day with an input, one task per part, on rayon's global thread pool.
+/// Returns the results in the order of the days and parts, and the wall-clock time it took to
+/// solve all of them.
+pub fn solve_all(inputs: &[(u8, String)], parts: RangeInclusive<u8>) -> (Vec<Solved>, Duration) {
+    let start = Instant::now();
+    let tasks: Vec<_> = inputs
+        .iter()
+        .flat_map(|(day, input)| parts.clone().map(move |part| (*day, part, input)))
+        .collect();
+    let solved = tasks
+        .into_par_iter()
+        .map(|(day, part, input)| {
+            let start = Instant::now();
+            let answer = days::solve(day, part, input);
+            Solved {
+                day,
+                part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+    (solved, start.elapsed())
+}
+
+/// Compares the wall-clock time to the time the parts took together, which is how long they
+/// would have taken one after the other.
+pub fn summary(solved: &[Solved], wall_clock: Duration) -> String {
+    let summed: Duration = solved.iter().map(|solved| solved.elapsed).sum();
+    let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+    format!(
+        "Solved {} parts in {} of wall-clock time, against {} summed over the parts ({:.1}x)",
+        solved.len(),
+        format_nanos(nanos(wall_clock)),
+        format_nanos(nanos(summed)),
+        summed.as_secs_f64() / wall_clock.as_secs_f64().max(f64::MIN_POSITIVE)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_all() {
+        let inputs = [
+            (
+                2,
+                String::from(include_str!("../../day_02/sample-input.txt")),
+            ),
+            (4, String::from("2-4,6-8\n2-3,4\n")),
+            (
+                5,
+                String::from(include_str!("../../day_05/sample-input.txt")),
+            ),
+        ];
+        let (solved, _) = solve_all(&inputs, 1..=2);
+        let tasks: Vec<_> = solved
+            .iter()
+            .map(|solved| (solved.day, solved.part))
+            .collect();
+        assert_eq!(tasks, [(2, 1), (2, 2), (4, 1), (4, 2), (5, 1), (5, 2)]);
+        assert_eq!(solved[1].answer, Ok(Answer::Integer(12)));
+        assert!(solved[3].answer.is_err());
+        assert_eq!(solved[4].answer, Ok(Answer::Text(String::from("CMZ"))));
+    }
+
+    #[test]
+    fn test_summary() {
+        let solved = [1, 2].map(|part| Solved {
+            day: 11,
+            part,
+            answer: Ok(Answer::Integer(0)),
+            elapsed: Duration::from_millis(30),
+        });
+        assert_eq!(
+            summary(&solved, Duration::from_millis(40)),
+            "Solved 2 parts in 40.0 ms of wall-clock time, against 60.0 ms summed over the parts \
+             (1.5x)"
+        );
+    }
+}
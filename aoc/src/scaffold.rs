@@ -0,0 +1,260 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_RS: &str = include_str!("../templates/lib.rs.template");
+const BENCH_RS: &str = include_str!("../templates/bench.rs.template");
+const FUZZ_TARGET_RS: &str = include_str!("../templates/fuzz_target.rs.template");
+const INPUTS_RS: &str = include_str!("../templates/inputs.rs.template");
+
+/// A file to create, or to replace with the day registered in it.
+#[derive(Debug)]
+pub struct Change {
+    pub path: PathBuf,
+    pub created: bool,
+    contents: String,
+}
+
+/// Works out the files to create and change for a new day in `workspace`: a crate like the
+/// other days, with stubs for both parts, tests against the known answers and the harness for
+/// the shared inputs, and the day added to the workspace members, the runner, the fuzz targets,
+/// the C library and the answers manifest.
+///
+/// Nothing is written yet, so a day that cannot be added leaves the workspace as it is. Days can
+/// only be added in order, because the runner looks them up by their position in its tables.
+pub fn new_day(workspace: &Path, day: u8) -> Result<Vec<Change>, String> {
+    let crate_dir = workspace.join(format!("day_{day:02}"));
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+    let previous = day - 1;
+    let mut changes = vec![
+        created(crate_dir.join("Cargo.toml"), render(CARGO_TOML, day)),
+        created(crate_dir.join("src/lib.rs"), render(LIB_RS, day)),
+        created(crate_dir.join("benches/bench.rs"), render(BENCH_RS, day)),
+        created(crate_dir.join("tests/inputs.rs"), render(INPUTS_RS, day)),
+        created(crate_dir.join("sample-input.txt"), String::new()),
+        created(
+            workspace.join(format!("fuzz/fuzz_targets/day_{day:02}.rs")),
+            render(FUZZ_TARGET_RS, day),
+        ),
+    ];
+
+    let path = workspace.join("Cargo.toml");
+    let text = read(&path)?;
+    let text = insert_after(
+        &text,
+        &format!("    \"day_{previous:02}\",\n"),
+        &format!("    \"day_{day:02}\",\n"),
+    );
+    changes.push(changed(path, text)?);
+
+    let dependency = |day: u8| format!("day_{day:02} = {{ path = \"../day_{day:02}\" }}\n");
+    let path = workspace.join("aoc/Cargo.toml");
+    let text = insert_after(&read(&path)?, &dependency(previous), &dependency(day));
+    changes.push(changed(path, text)?);
+
+    let path = workspace.join("fuzz/Cargo.toml");
+    let text = insert_after(&read(&path)?, &dependency(previous), &dependency(day)).map(|text| {
+        text + &format!(
+            "\n[[bin]]\nname = \"day_{day:02}\"\npath = \"fuzz_targets/day_{day:02}.rs\"\n\
+             test = false\ndoc = false\n"
+        )
+    });
+    changes.push(changed(path, text)?);
+
+    let path = workspace.join("ffi/Cargo.toml");
+    let text = insert_after(&read(&path)?, &dependency(previous), &dependency(day));
+    changes.push(changed(path, text)?);
+
+    let path = workspace.join("ffi/src/lib.rs");
+    let exports = |day: u8| {
+        format!(
+            "    day_{day:02}::Day{day:02} => aoc_day_{day:02}_part_1, aoc_day_{day:02}_part_2;\n"
+        )
+    };
+    let text = insert_after(&read(&path)?, &exports(previous), &exports(day));
+    changes.push(changed(path, text)?);
+
+    let path = workspace.join("ffi/include/aoc.h");
+    let prototype = |day: u8| {
+        format!("aoc_result aoc_day_{day:02}_part_2(const uint8_t *input, size_t len);\n")
+    };
+    let text = insert_after(
+        &read(&path)?,
+        &prototype(previous),
+        &format!(
+            "aoc_result aoc_day_{day:02}_part_1(const uint8_t *input, size_t len);\n{}",
+            prototype(day)
+        ),
+    );
+    changes.push(changed(path, text)?);
+
+    let path = workspace.join("aoc/src/days.rs");
+    let text = Some(read(&path)?)
+        .and_then(|text| {
+            replace(
+                &text,
+                &format!("pub const NUM_DAYS: u8 = {previous};\n"),
+                &format!("pub const NUM_DAYS: u8 = {day};\n"),
+            )
+        })
+        .and_then(|text| {
+            insert_after(
+                &text,
+                &format!("    day_{previous:02}::Day{previous:02}::try_solve,\n"),
+                &format!("    day_{day:02}::Day{day:02}::try_solve,\n"),
+            )
+        })
+        .and_then(|text| {
+            insert_after(
+                &text,
+                &format!("    time_day::<day_{previous:02}::Day{previous:02}>,\n"),
+                &format!("    time_day::<day_{day:02}::Day{day:02}>,\n"),
+            )
+        })
+        .and_then(|text| {
+            let table = text.find("const VISUALIZERS")?;
+            let end = table + text[table..].find("\n];\n")?;
+            Some(format!("{}\n    None,{}", &text[..end], &text[end..]))
+        });
+    changes.push(changed(path, text)?);
+
+    let path = workspace.join("answers.toml");
+    let text = read(&path)? + &format!("\n[day_{day:02}.sample]\n\n[day_{day:02}.input]\n");
+    changes.push(changed(path, Some(text))?);
+
+    Ok(changes)
+}
+
+/// Writes the files, creating directories as needed.
+pub fn apply(changes: &[Change]) -> Result<(), String> {
+    for change in changes {
+        if let Some(dir) = change.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|error| format!("could not create {}: {error}", dir.display()))?;
+        }
+        fs::write(&change.path, &change.contents)
+            .map_err(|error| format!("could not write {}: {error}", change.path.display()))?;
+    }
+    Ok(())
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{day:02}}", &format!("{day:02}"))
+        .replace("{{day}}", &day.to_string())
+}
+
+fn created(path: PathBuf, contents: String) -> Change {
+    Change {
+        path,
+        created: true,
+        contents,
+    }
+}
+
+/// The new contents of an existing file, or an error if the place of the new day in it was not
+/// found.
+fn changed(path: PathBuf, contents: Option<String>) -> Result<Change, String> {
+    match contents {
+        Some(contents) => Ok(Change {
+            path,
+            created: false,
+            contents,
+        }),
+        None => Err(format!(
+            "could not find where to add the day in {}; days can only be added after the last one",
+            path.display()
+        )),
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("could not read {}: {error}", path.display()))
+}
+
+fn insert_after(text: &str, line: &str, new_line: &str) -> Option<String> {
+    let end = text.find(line)? + line.len();
+    Some(format!("{}{new_line}{}", &text[..end], &text[end..]))
+}
+
+fn replace(text: &str, line: &str, new_line: &str) -> Option<String> {
+    text.contains(line)
+        .then(|| text.replacen(line, new_line, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::input::workspace_dir;
+
+    fn contents<'a>(changes: &'a [Change], path: &str) -> &'a str {
+        let change = changes
+            .iter()
+            .find(|change| change.path == workspace_dir().join(path))
+            .unwrap_or_else(|| panic!("no change to {path}"));
+        &change.contents
+    }
+
+    #[test]
+    fn test_new_day() {
+        let workspace = workspace_dir();
+        let day = (1..=25)
+            .find(|day| !workspace.join(format!("day_{day:02}")).exists())
+            .unwrap();
+        let changes = new_day(workspace, day).unwrap();
+
+        let created: Vec<_> = changes
+            .iter()
+            .filter(|change| change.created)
+            .map(|change| change.path.strip_prefix(workspace).unwrap())
+            .collect();
+        assert_eq!(created.len(), 6);
+        assert!(created.contains(&Path::new(&format!("day_{day:02}/src/lib.rs"))));
+
+        let lib = contents(&changes, &format!("day_{day:02}/src/lib.rs"));
+        assert!(lib.contains(&format!(
+            "impl Solution for Day{day:02} {{\n    const DAY: u8 = {day};"
+        )));
+        assert!(!lib.contains("{{"));
+        let inputs = contents(&changes, &format!("day_{day:02}/tests/inputs.rs"));
+        assert!(inputs.contains(&format!("run::<day_{day:02}::Day{day:02}>()")));
+
+        let days = contents(&changes, "aoc/src/days.rs");
+        assert!(days.contains(&format!("pub const NUM_DAYS: u8 = {day};")));
+        assert!(days.contains(&format!(
+            "    time_day::<day_{:02}::Day{:02}>,\n    time_day::<day_{day:02}::Day{day:02}>,\n];",
+            day - 1,
+            day - 1
+        )));
+        assert!(days.contains("    None,\n    None,\n];"));
+
+        let members = contents(&changes, "Cargo.toml");
+        assert!(members.contains(&format!("    \"day_{day:02}\",\n    \"aoc\",")));
+        let fuzz = contents(&changes, "fuzz/Cargo.toml");
+        assert!(fuzz.ends_with(&format!("name = \"day_{day:02}\"\npath = \"fuzz_targets/day_{day:02}.rs\"\ntest = false\ndoc = false\n")));
+        let ffi = contents(&changes, "ffi/src/lib.rs");
+        assert!(ffi.contains(&format!(
+            "    day_{day:02}::Day{day:02} => aoc_day_{day:02}_part_1, aoc_day_{day:02}_part_2;\n}}"
+        )));
+        let header = contents(&changes, "ffi/include/aoc.h");
+        assert!(header.contains(&format!(
+            "aoc_result aoc_day_{day:02}_part_2(const uint8_t *input, size_t len);\n\n#ifdef"
+        )));
+        let answers = contents(&changes, "answers.toml");
+        assert!(answers.ends_with(&format!(
+            "\n\n[day_{day:02}.sample]\n\n[day_{day:02}.input]\n"
+        )));
+    }
+
+    #[test]
+    fn test_new_day_out_of_order() {
+        let error = new_day(workspace_dir(), 25).unwrap_err();
+        assert!(error.contains("days can only be added after the last one"));
+        let error = new_day(workspace_dir(), 1).unwrap_err();
+        assert!(error.ends_with("day_01 already exists"));
+    }
+}
@@ -0,0 +1,528 @@
+use common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// The number of consecutive batches that must agree for a measurement to be stable.
+const WINDOW: usize = 5;
+
+/// A part is reported as a regression when it is this much slower than in the baseline.
+const REGRESSION_THRESHOLD: f64 = 0.1;
+
+/// How long to measure, and when to consider a measurement stable.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Batches are made at least this long, so that the clock resolution does not matter.
+    pub min_batch_time: Duration,
+    /// The largest relative spread between the last batches of a stable measurement.
+    pub tolerance: f64,
+    /// Measuring stops after this long, stable or not.
+    pub max_time: Duration,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            min_batch_time: Duration::from_millis(2),
+            tolerance: 0.02,
+            max_time: Duration::from_secs(1),
+        }
+    }
+}
+
+/// The time a single call takes, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub nanos: f64,
+    /// Whether the last batches agreed within the tolerance before the time ran out.
+    pub stable: bool,
+}
+
+/// Measures the time of a call to `f` by running it in batches until the mean time per call of
+/// the last few batches agrees, and returns their median.
+pub fn measure<T>(settings: &Settings, mut f: impl FnMut() -> T) -> Measurement {
+    let start = Instant::now();
+    let mut run_batch = |size: u32| {
+        let batch_start = Instant::now();
+        for _ in 0..size {
+            black_box(f());
+        }
+        batch_start.elapsed()
+    };
+
+    let mut batch_size = 1;
+    while run_batch(batch_size) < settings.min_batch_time && start.elapsed() < settings.max_time {
+        batch_size = batch_size.saturating_mul(2);
+    }
+
+    let mut samples = Vec::new();
+    loop {
+        samples.push(run_batch(batch_size).as_secs_f64() * 1e9 / f64::from(batch_size));
+        let mut window = samples[samples.len().saturating_sub(WINDOW)..].to_vec();
+        window.sort_by(f64::total_cmp);
+        let (min, max) = (window[0], window[window.len() - 1]);
+        let stable = window.len() == WINDOW && max - min <= settings.tolerance * min;
+        if stable || start.elapsed() >= settings.max_time {
+            return Measurement {
+                nanos: window[window.len() / 2],
+                stable,
+            };
+        }
+    }
+}
+
+/// What parsing the input and solving a part allocate, counted with the `count-allocations`
+/// feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Allocations {
+    pub count: u64,
+    /// The most bytes in use at once.
+    pub peak_bytes: u64,
+}
+
+/// The times to parse the input of a day and to solve each part from the parsed input.
+#[derive(Debug, Clone, Copy)]
+pub struct DayTimings {
+    pub parse: Measurement,
+    pub solve: [Measurement; 2],
+    /// The allocations of each part, parsing included, if they were counted.
+    pub allocations: Option<[Allocations; 2]>,
+}
+
+/// Times parsing and solving one day. The input is solved once first, so that an input without
+/// answer is reported instead of timed.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or one of the parts cannot be solved.
+pub fn time_day<S: Solution>(input: &str, settings: &Settings) -> Result<DayTimings, ParseError> {
+    let parsed = S::parse(input)?;
+    S::solve_1(input, &parsed)?;
+    S::solve_2(input, &parsed)?;
+    #[cfg(feature = "count-allocations")]
+    let allocations = Some([1, 2].map(|part| count_allocations::<S>(input, part)));
+    #[cfg(not(feature = "count-allocations"))]
+    let allocations = None;
+
+    Ok(DayTimings {
+        parse: measure(settings, || S::parse(black_box(input))),
+        solve: [
+            measure(settings, || S::solve_1(input, black_box(&parsed))),
+            measure(settings, || S::solve_2(input, black_box(&parsed))),
+        ],
+        allocations,
+    })
+}
+
+/// Counts the allocations of a part, from parsing the input to dropping the answer.
+#[cfg(feature = "count-allocations")]
+fn count_allocations<S: Solution>(input: &str, part: u8) -> Allocations {
+    let ((), allocations) = crate::alloc::count(|| {
+        let parsed = S::parse(input);
+        let answer = parsed.map(|parsed| match part {
+            1 => S::solve_1(input, &parsed).map(Into::into),
+            _ => S::solve_2(input, &parsed).map(Into::into),
+        });
+        drop(answer);
+    });
+    allocations
+}
+
+/// The timings of every part, as written to and read from JSON.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub total_ns: u64,
+    pub parts: Vec<PartTiming>,
+}
+
+/// The time to solve one part from the input, split into parsing and solving.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartTiming {
+    pub day: u8,
+    pub part: u8,
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+    pub total_ns: u64,
+    pub stable: bool,
+    /// Left out of the JSON if the allocations were not counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<Allocations>,
+}
+
+impl Report {
+    /// Adds both parts of a day. Each part includes the time to parse, since that is what
+    /// solving it from scratch takes.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn add_day(&mut self, day: u8, timings: &DayTimings) {
+        let parse_ns = timings.parse.nanos.round() as u64;
+        for (part, solve) in (1..).zip(timings.solve) {
+            let solve_ns = solve.nanos.round() as u64;
+            self.total_ns += parse_ns + solve_ns;
+            self.parts.push(PartTiming {
+                day,
+                part,
+                parse_ns,
+                solve_ns,
+                total_ns: parse_ns + solve_ns,
+                stable: timings.parse.stable && solve.stable,
+                allocations: timings
+                    .allocations
+                    .map(|allocations| allocations[usize::from(part) - 1]),
+            });
+        }
+    }
+
+    /// Formats the parts as a Markdown table ranked from slowest to fastest, with the total at
+    /// the bottom. Given a baseline, a column shows how much each part changed, followed by a
+    /// list of the parts that became slower than [`REGRESSION_THRESHOLD`].
+    #[must_use]
+    pub fn table(&self, baseline: Option<&Report>) -> String {
+        let mut ranked: Vec<_> = self.parts.iter().collect();
+        ranked.sort_by_key(|timing| Reverse(timing.total_ns));
+
+        let mut rows = vec![vec![
+            String::from("Rank"),
+            String::from("Day"),
+            String::from("Part"),
+            String::from("Parse"),
+            String::from("Solve"),
+            String::from("Total"),
+        ]];
+        let counted = ranked.iter().any(|timing| timing.allocations.is_some());
+        if counted {
+            rows[0].extend([String::from("Allocs"), String::from("Peak")]);
+        }
+        let mut regressions = Vec::new();
+        for (rank, timing) in (1..).zip(&ranked) {
+            let mut row = vec![
+                rank.to_string(),
+                timing.day.to_string(),
+                timing.part.to_string(),
+                format_nanos(timing.parse_ns),
+                format_nanos(timing.solve_ns),
+                format_nanos(timing.total_ns),
+            ];
+            if !timing.stable {
+                row[5].insert(0, '~');
+            }
+            if counted {
+                let allocations = timing.allocations.unwrap_or_default();
+                row.push(allocations.count.to_string());
+                row.push(format_bytes(allocations.peak_bytes));
+            }
+            if let Some(baseline) = baseline {
+                let change = baseline.change(timing);
+                if change.is_some_and(|change| change > REGRESSION_THRESHOLD) {
+                    regressions.push(format!("day {} part {}", timing.day, timing.part));
+                }
+                row.push(change.map_or_else(
+                    || String::from("new"),
+                    |change| format!("{:+.1}%", change * 100.0),
+                ));
+            }
+            rows.push(row);
+        }
+        let mut total_row = vec![String::new(), String::from("Total"), String::new()];
+        total_row.push(format_nanos(
+            ranked.iter().map(|timing| timing.parse_ns).sum(),
+        ));
+        total_row.push(format_nanos(
+            ranked.iter().map(|timing| timing.solve_ns).sum(),
+        ));
+        total_row.push(format_nanos(self.total_ns));
+        if counted {
+            let count = ranked
+                .iter()
+                .filter_map(|timing| timing.allocations)
+                .map(|allocations| allocations.count)
+                .sum::<u64>();
+            total_row.extend([count.to_string(), String::new()]);
+        }
+        if let Some(baseline) = baseline {
+            rows[0].push(String::from("Change"));
+            total_row.push(self.total_change(baseline));
+        }
+        rows.push(total_row);
+
+        let mut table = markdown_table(&rows);
+        if ranked.iter().any(|timing| !timing.stable) {
+            table.push_str("\n~ did not settle within the time limit");
+        }
+        if !regressions.is_empty() {
+            write!(
+                table,
+                "\nSlower than the baseline by more than {:.0}%: {}",
+                REGRESSION_THRESHOLD * 100.0,
+                regressions.join(", ")
+            )
+            .unwrap();
+        }
+        table
+    }
+
+    /// The relative change in the total time of a part since this report, if it has the part.
+    fn change(&self, timing: &PartTiming) -> Option<f64> {
+        self.find(timing)
+            .map(|old| relative_change(old.total_ns, timing.total_ns))
+    }
+
+    fn find(&self, timing: &PartTiming) -> Option<&PartTiming> {
+        self.parts
+            .iter()
+            .find(|old| (old.day, old.part) == (timing.day, timing.part))
+    }
+
+    /// The relative change in the total time since the baseline, formatted as a percentage. Only
+    /// the parts in both reports count, so that adding a day does not show up as a regression.
+    fn total_change(&self, baseline: &Report) -> String {
+        let (old_ns, new_ns) = self
+            .parts
+            .iter()
+            .filter_map(|timing| Some((baseline.find(timing)?.total_ns, timing.total_ns)))
+            .fold((0, 0), |(old_sum, new_sum), (old_ns, new_ns)| {
+                (old_sum + old_ns, new_sum + new_ns)
+            });
+        if old_ns == 0 {
+            String::new()
+        } else {
+            format!("{:+.1}%", relative_change(old_ns, new_ns) * 100.0)
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn relative_change(old_ns: u64, new_ns: u64) -> f64 {
+    (new_ns as f64 - old_ns as f64) / old_ns as f64
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn format_nanos(nanos: u64) -> String {
+    let nanos = nanos as f64;
+    if nanos < 1e3 {
+        format!("{nanos:.0} ns")
+    } else if nanos < 1e6 {
+        format!("{:.1} µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.1} ms", nanos / 1e6)
+    } else {
+        format!("{:.2} s", nanos / 1e9)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    let bytes_f64 = bytes as f64;
+    if bytes < 1 << 10 {
+        format!("{bytes} B")
+    } else if bytes < 1 << 20 {
+        format!("{:.1} KiB", bytes_f64 / f64::from(1 << 10))
+    } else if bytes < 1 << 30 {
+        format!("{:.1} MiB", bytes_f64 / f64::from(1 << 20))
+    } else {
+        format!("{:.2} GiB", bytes_f64 / f64::from(1 << 30))
+    }
+}
+
+/// Formats rows of cells as a Markdown table with the first row as header, padded so that it
+/// also reads well as plain text. Every column is aligned right.
+fn markdown_table(rows: &[Vec<String>]) -> String {
+    let num_columns = rows[0].len();
+    let widths: Vec<_> = (0..num_columns)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut table = String::new();
+    for (index, row) in rows.iter().enumerate() {
+        for (cell, &width) in row.iter().zip(&widths) {
+            write!(table, "| {cell:>width$} ").unwrap();
+        }
+        table.push_str("|\n");
+        if index == 0 {
+            for &width in &widths {
+                write!(table, "|{:-<1$}:", "", width + 1).unwrap();
+            }
+            table.push_str("|\n");
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_settings() -> Settings {
+        Settings {
+            min_batch_time: Duration::from_micros(10),
+            tolerance: 0.5,
+            max_time: Duration::from_millis(100),
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let measurement = measure(&fast_settings(), || black_box(7) * 6);
+        assert!(measurement.nanos > 0.0);
+        assert!(measurement.nanos < 1e6);
+    }
+
+    #[test]
+    fn test_time_day() {
+        let sample_input = include_str!("../../day_02/sample-input.txt");
+        let timings = time_day::<day_02::Day02>(sample_input, &fast_settings()).unwrap();
+        assert!(timings.parse.nanos > 0.0);
+        assert_eq!(
+            timings.allocations.is_some(),
+            cfg!(feature = "count-allocations")
+        );
+        if let Some([part_1, _]) = timings.allocations {
+            assert!(part_1.count > 0 && part_1.peak_bytes > 0);
+        }
+
+        let error = time_day::<day_12::Day12>("Sbc\ncdE\n", &fast_settings()).unwrap_err();
+        assert_eq!(error.expected, "a route from `S` to `E`");
+    }
+
+    fn measurement(nanos: f64) -> Measurement {
+        Measurement {
+            nanos,
+            stable: true,
+        }
+    }
+
+    fn report() -> Report {
+        let mut report = Report::default();
+        report.add_day(
+            1,
+            &DayTimings {
+                parse: measurement(20_000.0),
+                solve: [measurement(500.0), measurement(1_500.0)],
+                allocations: None,
+            },
+        );
+        report.add_day(
+            2,
+            &DayTimings {
+                parse: Measurement {
+                    nanos: 2_000_000.0,
+                    stable: false,
+                },
+                solve: [measurement(100.0), measurement(3_000_000.0)],
+                allocations: None,
+            },
+        );
+        report
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            report().table(None),
+            "\
+| Rank |   Day | Part |   Parse |  Solve |   Total |
+|-----:|------:|-----:|--------:|-------:|--------:|
+|    1 |     2 |    2 |  2.0 ms | 3.0 ms | ~5.0 ms |
+|    2 |     2 |    1 |  2.0 ms | 100 ns | ~2.0 ms |
+|    3 |     1 |    2 | 20.0 µs | 1.5 µs | 21.5 µs |
+|    4 |     1 |    1 | 20.0 µs | 500 ns | 20.5 µs |
+|      | Total |      |  4.0 ms | 3.0 ms |  7.0 ms |
+
+~ did not settle within the time limit"
+        );
+    }
+
+    #[test]
+    fn test_table_with_baseline() {
+        let baseline = report();
+        let mut report = Report::default();
+        report.add_day(
+            1,
+            &DayTimings {
+                parse: measurement(20_000.0),
+                solve: [measurement(5_000.0), measurement(1_500.0)],
+                allocations: None,
+            },
+        );
+        report.add_day(
+            3,
+            &DayTimings {
+                parse: measurement(1_000.0),
+                solve: [measurement(1_000.0), measurement(1_000.0)],
+                allocations: None,
+            },
+        );
+        assert_eq!(
+            report.table(Some(&baseline)),
+            "\
+| Rank |   Day | Part |   Parse |  Solve |   Total | Change |
+|-----:|------:|-----:|--------:|-------:|--------:|-------:|
+|    1 |     1 |    1 | 20.0 µs | 5.0 µs | 25.0 µs | +22.0% |
+|    2 |     1 |    2 | 20.0 µs | 1.5 µs | 21.5 µs |  +0.0% |
+|    3 |     3 |    1 |  1.0 µs | 1.0 µs |  2.0 µs |    new |
+|    4 |     3 |    2 |  1.0 µs | 1.0 µs |  2.0 µs |    new |
+|      | Total |      | 42.0 µs | 8.5 µs | 50.5 µs | +10.7% |
+
+Slower than the baseline by more than 10%: day 1 part 1"
+        );
+    }
+
+    #[test]
+    fn test_table_with_allocations() {
+        let mut report = Report::default();
+        report.add_day(
+            9,
+            &DayTimings {
+                parse: measurement(1_000.0),
+                solve: [measurement(2_000.0), measurement(5_000.0)],
+                allocations: Some([
+                    Allocations {
+                        count: 12,
+                        peak_bytes: 800,
+                    },
+                    Allocations {
+                        count: 30,
+                        peak_bytes: 3 << 20,
+                    },
+                ]),
+            },
+        );
+        assert_eq!(
+            report.table(None),
+            "\
+| Rank |   Day | Part |  Parse |  Solve |  Total | Allocs |    Peak |
+|-----:|------:|-----:|-------:|-------:|-------:|-------:|--------:|
+|    1 |     9 |    2 | 1.0 µs | 5.0 µs | 6.0 µs |     30 | 3.0 MiB |
+|    2 |     9 |    1 | 1.0 µs | 2.0 µs | 3.0 µs |     12 |   800 B |
+|      | Total |      | 2.0 µs | 7.0 µs | 9.0 µs |     42 |         |
+"
+        );
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""stable":true,"allocations":{"count":12,"peak_bytes":800}}"#));
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 << 30), "5.00 GiB");
+    }
+
+    #[test]
+    fn test_json() {
+        let report = report();
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.starts_with(r#"{"total_ns":7042100,"parts":[{"day":1,"part":1,"#));
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
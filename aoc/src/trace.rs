@@ -0,0 +1,191 @@
+use crate::{args::Trace, days};
+use common::{
+    trace::{self, Event, Value},
+    Answer, ParseError,
+};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{self, BufWriter, Write},
+    rc::Rc,
+};
+
+/// Solves parts with the events they emit written out as they happen, as text for `--trace` or
+/// as JSON Lines for `--trace-json`.
+pub struct Tracer {
+    output: Rc<RefCell<Output>>,
+}
+
+struct Output {
+    writer: Box<dyn Write>,
+    json: bool,
+    /// The first error writing the events, after which the rest are dropped.
+    error: Option<io::Error>,
+}
+
+impl Output {
+    fn write(&mut self, day: u8, part: u8, seq: u64, event: &Event) {
+        if self.error.is_some() {
+            return;
+        }
+        let line = if self.json {
+            json_line(day, part, seq, event)
+        } else {
+            text_line(day, part, seq, event)
+        };
+        if let Err(error) = writeln!(self.writer, "{line}") {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl Tracer {
+    pub fn create(trace: &Trace) -> Result<Self, String> {
+        let (writer, json): (Box<dyn Write>, _) = match trace {
+            Trace::Print => (Box::new(BufWriter::new(io::stdout())), false),
+            Trace::Json(path) => {
+                let file = File::create(path)
+                    .map_err(|error| format!("could not create {}: {error}", path.display()))?;
+                (Box::new(BufWriter::new(file)), true)
+            }
+        };
+        let output = Output {
+            writer,
+            json,
+            error: None,
+        };
+        Ok(Self {
+            output: Rc::new(RefCell::new(output)),
+        })
+    }
+
+    /// Solves one part like [`days::solve`], numbering its events from 1. The events are flushed
+    /// before this returns, so that they come before the answer when both are printed.
+    pub fn solve(&self, day: u8, part: u8, input: &str) -> Result<Answer, ParseError> {
+        let output = Rc::clone(&self.output);
+        let mut seq = 0;
+        let sink = move |event: Event| {
+            seq += 1;
+            output.borrow_mut().write(day, part, seq, &event);
+        };
+        let answer = trace::with_sink(sink, || days::solve(day, part, input));
+
+        let mut output = self.output.borrow_mut();
+        if let Err(error) = output.writer.flush() {
+            output.error.get_or_insert(error);
+        }
+        answer
+    }
+
+    /// Returns the first error writing the events, if there was one.
+    pub fn finish(self) -> io::Result<()> {
+        let mut output = self.output.borrow_mut();
+        match output.error.take() {
+            Some(error) => Err(error),
+            None => output.writer.flush(),
+        }
+    }
+}
+
+fn text_line(day: u8, part: u8, seq: u64, event: &Event) -> String {
+    format!("Day {day:02}, part {part} #{seq}: {event}")
+}
+
+fn json_line(day: u8, part: u8, seq: u64, event: &Event) -> String {
+    #[derive(Serialize)]
+    struct Line<'a> {
+        day: u8,
+        part: u8,
+        seq: u64,
+        event: &'a str,
+        fields: Fields<'a>,
+    }
+
+    serde_json::to_string(&Line {
+        day,
+        part,
+        seq,
+        event: event.name,
+        fields: Fields(&event.fields),
+    })
+    .unwrap()
+}
+
+/// The fields of an event as a JSON object, in the order they were given.
+struct Fields<'a>(&'a [(&'static str, Value)]);
+
+impl Serialize for Fields<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in self.0 {
+            match value {
+                Value::Signed(value) => map.serialize_entry(name, value)?,
+                Value::Unsigned(value) => map.serialize_entry(name, value)?,
+                Value::Bool(value) => map.serialize_entry(name, value)?,
+                Value::Text(text) => map.serialize_entry(name, text)?,
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn event() -> Event {
+        Event {
+            name: "move",
+            fields: vec![
+                ("crates", Value::from("ZN")),
+                ("to", Value::from(3_usize)),
+                ("x", Value::from(-1)),
+                ("done", Value::from(false)),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_text_line() {
+        assert_eq!(
+            text_line(5, 2, 12, &event()),
+            r#"Day 05, part 2 #12: move crates="ZN" to=3 x=-1 done=false"#
+        );
+    }
+
+    #[test]
+    fn test_json_line() {
+        assert_eq!(
+            json_line(5, 2, 12, &event()),
+            r#"{"day":5,"part":2,"seq":12,"event":"move","fields":{"crates":"ZN","to":3,"x":-1,"done":false}}"#
+        );
+    }
+
+    #[test]
+    fn test_tracer() {
+        let path = env::temp_dir().join(format!("aoc-trace-{}.jsonl", std::process::id()));
+        let tracer = Tracer::create(&Trace::Json(path.clone())).unwrap();
+        let sample_input = include_str!("../../day_05/sample-input.txt");
+        assert_eq!(
+            tracer.solve(5, 2, sample_input),
+            Ok(Answer::Text(String::from("MCD")))
+        );
+        assert_eq!(
+            tracer.solve(2, 1, include_str!("../../day_02/sample-input.txt")),
+            Ok(Answer::Integer(15))
+        );
+        tracer.finish().unwrap();
+
+        let json = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let lines: Vec<_> = json.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            r#"{"day":5,"part":2,"seq":1,"event":"move","fields":{"crates":"D","from":2,"to":1}}"#
+        );
+        assert!(lines[3].starts_with(r#"{"day":5,"part":2,"seq":4,"#));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::days;
+use common::{input, manifest::Manifest, Answer};
+use std::{fmt::Write, ops::RangeInclusive, path::Path};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    /// The answer differs from the known one, or the input could not be parsed.
+    Fail(String),
+    /// The input file or the known answer is missing.
+    Missing,
+}
+
+/// The results of checking both parts of a day against one input.
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u8,
+    pub input: String,
+    pub parts: [Status; 2],
+}
+
+/// Runs every solution against every input of `days` that has known answers in the manifest.
+pub fn verify(manifest: &Manifest, days: RangeInclusive<u8>, input_dir: &Path) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    for day in days {
+        for (name, answers) in manifest.inputs(day) {
+            let input = input::load_named_input(input_dir, day, name).ok();
+            let parts = [1, 2].map(|part| match (&input, answers.part(part)) {
+                (Some(input), Some(expected)) => check(day, part, input, expected),
+                _ => Status::Missing,
+            });
+            outcomes.push(Outcome {
+                day,
+                input: String::from(name),
+                parts,
+            });
+        }
+    }
+    outcomes
+}
+
+fn check(day: u8, part: u8, input: &str, expected: &Answer) -> Status {
+    match days::solve(day, part, input) {
+        Ok(answer) if answer == *expected => Status::Pass,
+        Ok(answer) if answer.is_multiline() || expected.is_multiline() => {
+            Status::Fail(format!("expected\n{expected}\nfound\n{answer}"))
+        }
+        Ok(answer) => Status::Fail(format!("expected {expected}, found {answer}")),
+        Err(error) => Status::Fail(format!("invalid input at {error}")),
+    }
+}
+
+/// Formats the outcomes as a matrix of days and inputs against parts, followed by the details of
+/// every failure and a count of each status.
+pub fn report(outcomes: &[Outcome]) -> String {
+    let input_width = outcomes
+        .iter()
+        .map(|outcome| outcome.input.len())
+        .chain(["Input".len()])
+        .max()
+        .unwrap();
+    let mut report = format!("Day  {:input_width$}  Part 1   Part 2\n", "Input");
+    let mut failures = String::new();
+    let mut counts = [0; 3];
+
+    for outcome in outcomes {
+        write!(
+            report,
+            "{:02}   {:input_width$}",
+            outcome.day, outcome.input
+        )
+        .unwrap();
+        for (part, status) in (1..).zip(&outcome.parts) {
+            let (label, index) = match status {
+                Status::Pass => ("pass", 0),
+                Status::Fail(message) => {
+                    writeln!(
+                        failures,
+                        "Day {:02}, part {part}, input `{}`: {message}",
+                        outcome.day, outcome.input
+                    )
+                    .unwrap();
+                    ("FAIL", 1)
+                }
+                Status::Missing => ("missing", 2),
+            };
+            counts[index] += 1;
+            write!(report, "  {label:7}").unwrap();
+        }
+        report.truncate(report.trim_end().len());
+        report.push('\n');
+    }
+
+    if !failures.is_empty() {
+        write!(report, "\n{failures}").unwrap();
+    }
+    write!(
+        report,
+        "\n{} passed, {} failed, {} missing",
+        counts[0], counts[1], counts[2]
+    )
+    .unwrap();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[day_02.sample]
+part_1 = 15
+part_2 = 13
+
+[day_05.sample]
+part_1 = "CMZ"
+
+[day_05.nobody]
+part_1 = "CMZ"
+"#;
+
+    #[test]
+    fn test_verify() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let outcomes = verify(&manifest, 1..=13, Path::new("no-such-directory"));
+        let statuses: Vec<_> = outcomes.iter().map(|outcome| &outcome.parts).collect();
+        assert_eq!(
+            statuses,
+            [
+                &[
+                    Status::Pass,
+                    Status::Fail(String::from("expected 13, found 12"))
+                ],
+                &[Status::Missing, Status::Missing],
+                &[Status::Pass, Status::Missing],
+            ]
+        );
+
+        assert_eq!(
+            report(&outcomes),
+            "\
+Day  Input   Part 1   Part 2
+02   sample  pass     FAIL
+05   nobody  missing  missing
+05   sample  pass     missing
+
+Day 02, part 2, input `sample`: expected 13, found 12
+
+2 passed, 1 failed, 3 missing"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+/// The answer to one part of a puzzle. Most days produce a number, but day 5 spells out the
+/// crates on top of each stack and day 10 draws its answer on the CRT.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+}
+
+impl Answer {
+    #[must_use]
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Text(text) if text.trim_end().contains('\n'))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            Self::Integer(integer) => write!(formatter, "{integer}"),
+            Self::Text(text) => write!(formatter, "{}", text.trim_end()),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(integer: u32) -> Self {
+        Self::Integer(i64::from(integer))
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(integer: i32) -> Self {
+        Self::Integer(i64::from(integer))
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(integer: i64) -> Self {
+        Self::Integer(integer)
+    }
+}
+
+/// Integers beyond the range of `i64` become text, which is also how TOML has to store them.
+impl From<u64> for Answer {
+    fn from(integer: u64) -> Self {
+        i64::try_from(integer).map_or_else(|_| Self::Text(integer.to_string()), Self::Integer)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(integer: usize) -> Self {
+        Self::from(integer as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(70_698_u32).to_string(), "70698");
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(String::from("CMZ")).to_string(), "CMZ");
+        assert_eq!(Answer::from(String::from("#.\n.#\n")).to_string(), "#.\n.#");
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::Text(String::from("18446744073709551615"))
+        );
+    }
+
+    #[test]
+    fn test_is_multiline() {
+        assert!(!Answer::from(String::from("CMZ\n")).is_multiline());
+        assert!(Answer::from(String::from("#.\n.#\n")).is_multiline());
+        assert!(!Answer::from(13_u32).is_multiline());
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+};
+
+/// Describes where and why a puzzle input could not be parsed.
+///
+/// Line and column numbers start at 1, and columns count characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub line_text: String,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Reports that `found` was not what was `expected`. `found` must be a slice of `input`, so
+    /// that its position can be worked out. Pass an empty slice to report something missing; the
+    /// character at that position, or the end of the line, is then reported as found.
+    #[must_use]
+    pub fn at(input: &str, found: &str, expected: impl Into<String>) -> Self {
+        let offset = (found.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        assert!(
+            offset <= input.len() && offset + found.len() <= input.len(),
+            "`found` must be a slice of `input`"
+        );
+        Self::at_offset(input, offset, found.len(), expected)
+    }
+
+    /// Reports that the character that `rest` starts with was not what was `expected`. `rest`
+    /// must be a slice of `input`; if it is empty, the end of the line or input is reported.
+    #[must_use]
+    pub fn at_char(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let found_len = rest.chars().next().map_or(0, char::len_utf8);
+        Self::at(input, &rest[..found_len], expected)
+    }
+
+    /// Reports that the `len` bytes at `offset` in `input` were not what was `expected`. The
+    /// range is widened to the nearest character boundaries when it splits a character.
+    #[must_use]
+    pub fn at_offset(input: &str, offset: usize, len: usize, expected: impl Into<String>) -> Self {
+        let mut start = offset.min(input.len());
+        while !input.is_char_boundary(start) {
+            start -= 1;
+        }
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+
+        let mut end = (offset + len).min(input.len());
+        if end == start && start < line_end {
+            end += 1;
+        }
+        while !input.is_char_boundary(end) {
+            end += 1;
+        }
+        let found = &input[start..end];
+
+        Self {
+            line: input[..start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            line_text: String::from(&input[line_start..line_end]),
+            found: if found.is_empty() && line_end < input.len() {
+                String::from("end of line")
+            } else if found.is_empty() {
+                String::from("end of input")
+            } else {
+                format!("`{}`", found.escape_debug())
+            },
+            expected: expected.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        let line_number = self.line.to_string();
+        let margin = " ".repeat(line_number.len());
+        let underline_len = self
+            .found
+            .strip_prefix('`')
+            .and_then(|found| found.strip_suffix('`'))
+            .map_or(1, |found| found.chars().count().max(1));
+        writeln!(
+            formatter,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )?;
+        writeln!(formatter, "{margin} |")?;
+        writeln!(formatter, "{line_number} | {}", self.line_text)?;
+        write!(
+            formatter,
+            "{margin} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(underline_len)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "A Y\nB X\nC Q\n";
+
+    #[test]
+    fn test_at() {
+        let error = ParseError::at(INPUT, &INPUT[10..11], "`X`, `Y` or `Z`");
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.line_text, "C Q");
+        assert_eq!(error.found, "`Q`");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 3: expected `X`, `Y` or `Z`, found `Q`
+  |
+3 | C Q
+  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_at_char() {
+        let input = "1-2,3-é";
+        let error = ParseError::at_char(input, &input[6..], "a number");
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.found, "`é`");
+    }
+
+    #[test]
+    fn test_missing() {
+        let error = ParseError::at_char(INPUT, &INPUT[3..3], "a space");
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.found, "end of line");
+
+        let error = ParseError::at(INPUT, &INPUT[4..4], "`A`, `B` or `C`");
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "`B`");
+
+        let error = ParseError::at(INPUT, &INPUT[INPUT.len()..], "another round");
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.found, "end of input");
+    }
+
+    #[test]
+    fn test_at_offset_inside_char() {
+        let error = ParseError::at_offset("aéb", 2, 1, "an ASCII letter");
+        assert_eq!(error.column, 2);
+        assert_eq!(error.found, "`é`");
+    }
+
+    #[test]
+    #[should_panic = "`found` must be a slice of `input`"]
+    fn test_at_foreign_slice() {
+        let _ = ParseError::at(INPUT, "Q", "a letter");
+    }
+}
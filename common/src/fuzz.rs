@@ -0,0 +1,108 @@
+use crate::Solution;
+use proptest::{collection::vec, prelude::*};
+
+/// Characters that mean something in at least one puzzle, and a few that mean nothing anywhere.
+const INTERESTING: &[char] = &[
+    '0', '1', '5', '9', '-', '+', '*', ',', ' ', '\n', '\r', '[', ']', '$', ':', '/', '.', 'a',
+    'z', 'A', 'S', 'E', 'X', '\t', 'é', '\u{feff}',
+];
+
+/// A change to one position of an input.
+#[derive(Debug, Clone)]
+pub enum Mutation {
+    Insert(char),
+    Replace(char),
+    Delete(usize),
+    /// Repeats the next characters at the position, up to the given number.
+    Duplicate(usize),
+}
+
+fn interesting_char() -> impl Strategy<Value = char> {
+    prop_oneof![
+        3 => proptest::sample::select(INTERESTING),
+        1 => any::<char>(),
+    ]
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        interesting_char().prop_map(Mutation::Insert),
+        interesting_char().prop_map(Mutation::Replace),
+        (1..20_usize).prop_map(Mutation::Delete),
+        (1..40_usize).prop_map(Mutation::Duplicate),
+    ]
+}
+
+/// Applies the mutations, each at a position given as a fraction of the length of the input.
+#[must_use]
+pub fn mutate(input: &str, mutations: &[(f64, Mutation)]) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for (position, mutation) in mutations {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let i = ((position * chars.len() as f64) as usize).min(chars.len());
+        let end = |len: usize| (i + len).min(chars.len());
+        match *mutation {
+            Mutation::Insert(c) => chars.insert(i, c),
+            Mutation::Replace(c) if i < chars.len() => chars[i] = c,
+            Mutation::Replace(c) => chars.push(c),
+            Mutation::Delete(len) => drop(chars.drain(i..end(len))),
+            Mutation::Duplicate(len) => {
+                let repeated = chars[i..end(len)].to_vec();
+                chars.splice(i..i, repeated);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Valid inputs from a generator, with a few random mutations that will usually make them
+/// invalid in a way that is close to valid. This finds more parser bugs than random text does.
+pub fn mutated_input(
+    generate: fn(usize, u64) -> String,
+    max_size: usize,
+) -> impl Strategy<Value = String> {
+    (
+        0..max_size,
+        any::<u64>(),
+        vec((0.0..=1.0, mutation()), 0..4),
+    )
+        .prop_map(move |(size, seed, mutations)| mutate(&generate(size, seed), &mutations))
+}
+
+/// The input as other editors may save it: with `\r\n` line endings, without the final line
+/// ending, and with whitespace at the end of lines and blank lines at the end of the input. The
+/// solutions should give the same answers for all of them.
+#[must_use]
+pub fn whitespace_variants(input: &str) -> [String; 3] {
+    [
+        input.replace('\n', "\r\n"),
+        input.trim_end().to_owned(),
+        input.replace('\n', " \t\n") + "\n \n\n",
+    ]
+}
+
+/// Solves both parts, discarding the results. A solution passes if this does not panic.
+pub fn solve_both<S: Solution>(input: &str) {
+    let _ = S::try_part_1(input);
+    let _ = S::try_part_2(input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate() {
+        let mutations = [
+            (0.0, Mutation::Insert('[')),
+            (1.0, Mutation::Replace('!')),
+            (0.5, Mutation::Delete(2)),
+            (0.0, Mutation::Duplicate(3)),
+        ];
+        assert_eq!(mutate("abcdef", &mutations), "[ab[abcf!");
+    }
+}
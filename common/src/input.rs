@@ -0,0 +1,212 @@
+use std::{
+    borrow::Cow,
+    env,
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable that overrides the directory with the puzzle inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The name of the input of each day in `day_NN/input.txt`.
+pub const DEFAULT_INPUT: &str = "input";
+
+/// The root of the workspace, with a `day_NN` directory for each day.
+#[must_use]
+pub fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// The directory with the puzzle inputs: `$AOC_INPUT_DIR` if it is set, the root of the
+/// workspace otherwise. Either way, the input of each day is in `day_NN/input.txt` below it.
+#[must_use]
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR).map_or_else(|| workspace_dir().to_path_buf(), PathBuf::from)
+}
+
+#[must_use]
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    named_input_path(dir, day, DEFAULT_INPUT)
+}
+
+/// The file with a named input of a day. [`DEFAULT_INPUT`] is `day_NN/input.txt` below `dir`,
+/// and any other name is one of the inputs shared in `day_NN/inputs/`. Names ending in `sample`
+/// are the examples from the puzzle description, which are checked in next to the code as
+/// `day_NN/<name>-input.txt`, so they are always found in the workspace.
+#[must_use]
+pub fn named_input_path(dir: &Path, day: u8, name: &str) -> PathBuf {
+    let day_dir = format!("day_{day:02}");
+    if name == DEFAULT_INPUT {
+        dir.join(day_dir).join("input.txt")
+    } else if name.ends_with("sample") {
+        workspace_dir()
+            .join(day_dir)
+            .join(format!("{name}-input.txt"))
+    } else {
+        dir.join(day_dir).join("inputs").join(format!("{name}.txt"))
+    }
+}
+
+/// Reads the puzzle input of a day from [`input_dir`].
+///
+/// # Errors
+///
+/// Returns an error if the input file cannot be read.
+pub fn load_input(day: u8) -> Result<String, InputError> {
+    load_input_from(&input_dir(), day)
+}
+
+/// Reads the puzzle input of a day from `dir`.
+///
+/// # Errors
+///
+/// Returns an error if the input file cannot be read.
+pub fn load_input_from(dir: &Path, day: u8) -> Result<String, InputError> {
+    read_input(day, input_path(dir, day))
+}
+
+/// Reads a named input of a day from `dir`; see [`named_input_path`].
+///
+/// # Errors
+///
+/// Returns an error if the input file cannot be read.
+pub fn load_named_input(dir: &Path, day: u8, name: &str) -> Result<String, InputError> {
+    read_input(day, named_input_path(dir, day, name))
+}
+
+/// Reads the puzzle input of a day from a file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn read_input(day: u8, path: PathBuf) -> Result<String, InputError> {
+    let input = fs::read_to_string(&path).map_err(|source| InputError { day, path, source })?;
+    Ok(match normalize(&input) {
+        Cow::Borrowed(_) => input,
+        Cow::Owned(normalized) => normalized,
+    })
+}
+
+/// Brings an input into the shape the solutions expect, whatever editor it went through: lines
+/// end in `\n` rather than `\r\n`, no line ends in whitespace, and there are no blank lines at
+/// the end. A last line without line ending is left without one.
+///
+/// Only the ends of lines and the end of the input change, so errors in the normalized input
+/// point at the same lines and columns as in the original.
+#[must_use]
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let is_normalized = input != "\n"
+        && !input.ends_with("\n\n")
+        && input
+            .split_terminator('\n')
+            .all(|line| !line.ends_with(char::is_whitespace));
+    if is_normalized {
+        return Cow::Borrowed(input);
+    }
+
+    let content = input.trim_end();
+    let mut normalized = String::with_capacity(content.len() + 1);
+    for (i, line) in content.lines().enumerate() {
+        if i > 0 {
+            normalized.push('\n');
+        }
+        normalized.push_str(line.trim_end());
+    }
+    if !content.is_empty() && input[content.len()..].contains('\n') {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+/// Reads the puzzle input of a day for a test that needs it. Puzzle inputs are personal and may
+/// not be checked in, so when it is missing this reports that the test is skipped and returns
+/// `None`.
+#[must_use]
+pub fn real_input(day: u8) -> Option<String> {
+    load_input(day)
+        .map_err(|error| eprintln!("skipping test: {error}"))
+        .ok()
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub day: u8,
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        write!(
+            formatter,
+            "could not read the input of day {} from {}: {}",
+            self.day,
+            self.path.display(),
+            self.source
+        )?;
+        if self.source.kind() == io::ErrorKind::NotFound {
+            write!(
+                formatter,
+                " (save it there, or set {INPUT_DIR_VAR} to a directory with a day_{:02}/input.txt)",
+                self.day
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        let path = input_path(Path::new("inputs"), 7);
+        assert_eq!(path, Path::new("inputs/day_07/input.txt"));
+
+        let path = named_input_path(Path::new("inputs"), 7, "alice");
+        assert_eq!(path, Path::new("inputs/day_07/inputs/alice.txt"));
+
+        let path = named_input_path(Path::new("inputs"), 9, "large-sample");
+        assert!(path.ends_with("day_09/large-sample-input.txt"));
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_normalize() {
+        for input in ["", "a", "a\n", "a\n\n b\n"] {
+            assert!(matches!(normalize(input), Cow::Borrowed(_)), "{input:?}");
+        }
+        for (input, normalized) in [
+            ("a\r\nb\r\n", "a\nb\n"),
+            ("a\n\nb ", "a\n\nb"),
+            ("a\nb\n\n \n\t\n", "a\nb\n"),
+            ("  a \t\n b\r\n", "  a\n b\n"),
+            ("\n", ""),
+            (" \r\n\r\n", ""),
+        ] {
+            assert_eq!(normalize(input), normalized, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let error = load_input_from(Path::new("no-such-directory"), 3).unwrap_err();
+        assert_eq!(error.day, 3);
+        let message = error.to_string();
+        assert!(message.starts_with(
+            "could not read the input of day 3 from no-such-directory/day_03/input.txt: "
+        ));
+        assert!(message.ends_with(
+            "(save it there, or set AOC_INPUT_DIR to a directory with a day_03/input.txt)"
+        ));
+    }
+}
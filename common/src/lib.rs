@@ -0,0 +1,173 @@
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)]
+
+mod answer;
+mod error;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod input;
+pub mod manifest;
+#[cfg(feature = "regression")]
+pub mod regression;
+pub mod rng;
+pub mod stream;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "visualize")]
+pub mod visualize;
+
+pub use answer::Answer;
+pub use error::ParseError;
+
+/// Without the `trace` feature, events are not emitted. The values are still borrowed in code that
+/// never runs, so that variables kept only for the events are not reported as unused.
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! trace {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        if false {
+            let _ = ($(&$value,)*);
+        }
+    };
+}
+
+/// The puzzle of one day of the calendar, with a solution for each of its two parts.
+///
+/// Solving is split into parsing the input, which both parts share, and solving each part from
+/// the parsed input, so that the two can be timed separately.
+pub trait Solution {
+    /// The day of the calendar, starting at 1.
+    const DAY: u8;
+
+    /// The input after parsing, which may borrow from the input.
+    type Parsed<'a>;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    /// # Errors
+    ///
+    /// Returns an error pointing at the first part of the input that does not match the puzzle.
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+
+    /// Solves part 1 from the parsed `input`, which is passed along to point at in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the part of the input that leaves the puzzle without answer.
+    fn solve_1<'a>(input: &'a str, parsed: &Self::Parsed<'a>) -> Result<Self::Answer1, ParseError>;
+
+    /// Solves part 2 from the parsed `input`, which is passed along to point at in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the part of the input that leaves the puzzle without answer.
+    fn solve_2<'a>(input: &'a str, parsed: &Self::Parsed<'a>) -> Result<Self::Answer2, ParseError>;
+
+    /// Solves part 1 from the input after [`input::normalize`], so that line endings and
+    /// whitespace at the end do not matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the first part of the input that does not match the puzzle.
+    fn try_part_1(input: &str) -> Result<Self::Answer1, ParseError> {
+        let input = input::normalize(input);
+        let parsed = Self::parse(&input)?;
+        Self::solve_1(&input, &parsed)
+    }
+
+    /// Solves part 2 from the input after [`input::normalize`], so that line endings and
+    /// whitespace at the end do not matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the first part of the input that does not match the puzzle.
+    fn try_part_2(input: &str) -> Result<Self::Answer2, ParseError> {
+        let input = input::normalize(input);
+        let parsed = Self::parse(&input)?;
+        Self::solve_2(&input, &parsed)
+    }
+
+    /// # Panics
+    ///
+    /// Panics with a report of the parse error if the input does not match the puzzle.
+    #[must_use]
+    fn part_1(input: &str) -> Self::Answer1 {
+        Self::try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// # Panics
+    ///
+    /// Panics with a report of the parse error if the input does not match the puzzle.
+    #[must_use]
+    fn part_2(input: &str) -> Self::Answer2 {
+        Self::try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Solves the given part (1 or 2) and converts the result into an [`Answer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the first part of the input that does not match the puzzle.
+    fn try_solve(part: u8, input: &str) -> Result<Answer, ParseError> {
+        match part {
+            1 => Self::try_part_1(input).map(Into::into),
+            2 => Self::try_part_2(input).map(Into::into),
+            _ => panic!("Day {} has no part {part}", Self::DAY),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        const DAY: u8 = 0;
+        type Parsed<'a> = Vec<&'a str>;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+            Ok(input.lines().collect())
+        }
+
+        fn solve_1(_input: &str, lines: &Vec<&str>) -> Result<usize, ParseError> {
+            Ok(lines.len())
+        }
+
+        fn solve_2(input: &str, lines: &Vec<&str>) -> Result<String, ParseError> {
+            match input.find(char::is_numeric) {
+                Some(i) => Err(ParseError::at_char(input, &input[i..], "a letter")),
+                None => Ok(lines.iter().rev().copied().collect()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_solve() {
+        assert_eq!(LineCount::try_solve(1, "a\nb\n"), Ok(Answer::Integer(2)));
+        assert_eq!(
+            LineCount::try_solve(2, "a\nb\n"),
+            Ok(Answer::Text(String::from("ba")))
+        );
+        assert_eq!(LineCount::try_solve(2, "a\n7\n").unwrap_err().line, 2);
+        assert_eq!(
+            LineCount::try_solve(1, "a\r\nb\r\n\r\n"),
+            Ok(Answer::Integer(2))
+        );
+    }
+
+    #[test]
+    #[should_panic = "line 1, column 2: expected a letter, found `1`"]
+    fn test_part_panics_with_report() {
+        let _ = LineCount::part_2("a1");
+    }
+
+    #[test]
+    #[should_panic = "Day 0 has no part 3"]
+    fn test_solve_invalid_part() {
+        let _ = LineCount::try_solve(3, "");
+    }
+}
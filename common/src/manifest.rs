@@ -0,0 +1,370 @@
+use crate::{input::workspace_dir, Answer};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The known answers, keyed by day, input name and part, as in
+///
+/// ```toml
+/// [day_05.input]
+/// part_1 = "ZRLJGSCTR"
+/// part_2 = "PRTTGRFPB"
+/// ```
+///
+/// The input names are resolved with [`named_input_path`](crate::input::named_input_path).
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    days: BTreeMap<u8, BTreeMap<String, InputAnswers>>,
+}
+
+/// The known answers for one input of a day. A part can be left out while its answer is unknown.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InputAnswers {
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+impl InputAnswers {
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl Manifest {
+    /// The manifest that is checked in at the root of the workspace.
+    #[must_use]
+    pub fn path() -> PathBuf {
+        workspace_dir().join("answers.toml")
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let text = fs::read_to_string(path).map_err(|source| ManifestError {
+            path: path.to_path_buf(),
+            kind: ManifestErrorKind::Read(source),
+        })?;
+        Self::parse(&text).map_err(|kind| ManifestError {
+            path: path.to_path_buf(),
+            kind,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid manifest.
+    pub fn parse(text: &str) -> Result<Self, ManifestErrorKind> {
+        let tables: BTreeMap<String, BTreeMap<String, InputAnswers>> =
+            toml::from_str(text).map_err(ManifestErrorKind::Parse)?;
+        let days = tables
+            .into_iter()
+            .map(
+                |(key, inputs)| match key.strip_prefix("day_").map(str::parse) {
+                    Some(Ok(day @ 1..=25)) => Ok((day, inputs)),
+                    _ => Err(ManifestErrorKind::InvalidDay(key)),
+                },
+            )
+            .collect::<Result<_, _>>()?;
+        Ok(Self { days })
+    }
+
+    /// The names of the inputs with known answers for a day.
+    pub fn inputs(&self, day: u8) -> impl Iterator<Item = (&str, &InputAnswers)> {
+        self.days
+            .get(&day)
+            .into_iter()
+            .flatten()
+            .map(|(name, answers)| (name.as_str(), answers))
+    }
+
+    #[must_use]
+    pub fn answer(&self, day: u8, part: u8, input: &str) -> Option<&Answer> {
+        self.days.get(&day)?.get(input)?.part(part)
+    }
+
+    /// Adds an answer to the manifest file at `path`, or replaces the one that is there, leaving
+    /// the rest of the file as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or written, or is not valid before or
+    /// after the change.
+    pub fn record(
+        path: &Path,
+        day: u8,
+        part: u8,
+        input: &str,
+        answer: &Answer,
+    ) -> Result<(), ManifestError> {
+        let error = |kind| ManifestError {
+            path: path.to_path_buf(),
+            kind,
+        };
+        let text =
+            fs::read_to_string(path).map_err(|source| error(ManifestErrorKind::Read(source)))?;
+        Self::parse(&text).map_err(error)?;
+        let text = with_answer(&text, day, part, input, answer);
+        Self::parse(&text).map_err(error)?;
+        fs::write(path, text).map_err(|source| error(ManifestErrorKind::Write(source)))
+    }
+}
+
+/// The text of a manifest with an answer added under `[day_NN.<input>]`, after the answer to the
+/// other part if it is there, or replacing an answer to the same part. A missing table is added
+/// at the end.
+fn with_answer(text: &str, day: u8, part: u8, input: &str, answer: &Answer) -> String {
+    let header = format!("[day_{day:02}.{input}]");
+    let key = format!("part_{part} =");
+    let entry = format!("part_{part} = {}", toml_value(answer));
+    let lines: Vec<&str> = text.lines().collect();
+
+    let Some(start) = lines.iter().position(|line| line.trim() == header) else {
+        let separator = if text.is_empty() || text.ends_with("\n\n") {
+            ""
+        } else if text.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        return format!("{text}{separator}{header}\n{entry}\n");
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.starts_with('['))
+        .map_or(lines.len(), |i| start + 1 + i);
+    // The end of the last entry of the table, which may be a multi-line string.
+    let last_entry = lines[start + 1..end]
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(start, |i| start + 1 + i);
+
+    let mut new_lines: Vec<&str> = Vec::with_capacity(lines.len() + 1);
+    match lines[start + 1..end]
+        .iter()
+        .position(|line| line.starts_with(&key))
+    {
+        Some(i) => {
+            // Replace the old answer, up to the next entry or the end of the table.
+            let old = start + 1 + i;
+            let next = lines[old + 1..=last_entry]
+                .iter()
+                .position(|line| line.starts_with("part_"))
+                .map_or(last_entry + 1, |i| old + 1 + i);
+            new_lines.extend(&lines[..old]);
+            new_lines.push(&entry);
+            new_lines.extend(&lines[next..]);
+        }
+        None if part == 1 => {
+            new_lines.extend(&lines[..=start]);
+            new_lines.push(&entry);
+            new_lines.extend(&lines[start + 1..]);
+        }
+        None => {
+            new_lines.extend(&lines[..=last_entry]);
+            new_lines.push(&entry);
+            new_lines.extend(&lines[last_entry + 1..]);
+        }
+    }
+    new_lines.join("\n") + "\n"
+}
+
+/// An answer as the manifest writes it: integers with `_` between groups of three digits, and
+/// text in quotes, or in triple quotes if it has more than one line.
+fn toml_value(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(integer) => {
+            let digits = integer.unsigned_abs().to_string();
+            let mut value = String::from(if *integer < 0 { "-" } else { "" });
+            for (i, digit) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i) % 3 == 0 {
+                    value.push('_');
+                }
+                value.push(digit);
+            }
+            value
+        }
+        Answer::Text(text) if text.contains('\n') && !text.contains("\"\"\"") => {
+            format!("\"\"\"\n{text}\"\"\"")
+        }
+        Answer::Text(text) => toml::Value::String(text.clone()).to_string(),
+    }
+}
+
+/// Looks up a known answer in the checked-in manifest, for tests.
+///
+/// # Panics
+///
+/// Panics if the manifest cannot be loaded, or if it has no answer for this part and input.
+#[must_use]
+pub fn expected_answer(day: u8, part: u8, input: &str) -> Answer {
+    let manifest = Manifest::load(&Manifest::path()).unwrap_or_else(|error| panic!("{error}"));
+    manifest
+        .answer(day, part, input)
+        .unwrap_or_else(|| panic!("no answer to day {day}, part {part} for input `{input}`"))
+        .clone()
+}
+
+#[derive(Debug)]
+pub struct ManifestError {
+    pub path: PathBuf,
+    pub kind: ManifestErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ManifestErrorKind {
+    Read(io::Error),
+    Write(io::Error),
+    Parse(toml::de::Error),
+    InvalidDay(String),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        write!(
+            formatter,
+            "invalid answers manifest {}: ",
+            self.path.display()
+        )?;
+        match &self.kind {
+            ManifestErrorKind::Read(error) | ManifestErrorKind::Write(error) => {
+                write!(formatter, "{error}")
+            }
+            ManifestErrorKind::Parse(error) => write!(formatter, "{error}"),
+            ManifestErrorKind::InvalidDay(key) => {
+                write!(formatter, "expected a table `day_NN`, found `{key}`")
+            }
+        }
+    }
+}
+
+impl Error for ManifestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ManifestErrorKind::Read(error) | ManifestErrorKind::Write(error) => Some(error),
+            ManifestErrorKind::Parse(error) => Some(error),
+            ManifestErrorKind::InvalidDay(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[day_05.input]
+part_1 = "ZRLJGSCTR"
+
+[day_07.alice]
+part_1 = 1_443_806
+part_2 = 942_298
+"#;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        assert_eq!(
+            manifest.answer(5, 1, "input"),
+            Some(&Answer::Text(String::from("ZRLJGSCTR")))
+        );
+        assert_eq!(manifest.answer(5, 2, "input"), None);
+        assert_eq!(
+            manifest.answer(7, 2, "alice"),
+            Some(&Answer::Integer(942_298))
+        );
+        let inputs: Vec<_> = manifest.inputs(7).map(|(name, _)| name).collect();
+        assert_eq!(inputs, ["alice"]);
+        assert_eq!(manifest.inputs(8).count(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Manifest::parse("[day_5x.input]\npart_1 = 1\n"),
+            Err(ManifestErrorKind::InvalidDay(key)) if key == "day_5x"
+        ));
+        assert!(matches!(
+            Manifest::parse("[day_05.input]\npart_3 = 1\n"),
+            Err(ManifestErrorKind::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_answer() {
+        let text = with_answer(
+            MANIFEST,
+            5,
+            2,
+            "input",
+            &Answer::Text(String::from("PRTTGRFPB")),
+        );
+        assert!(text.contains("[day_05.input]\npart_1 = \"ZRLJGSCTR\"\npart_2 = \"PRTTGRFPB\"\n\n"));
+
+        let text = with_answer(&text, 7, 1, "alice", &Answer::Integer(-1_234_567));
+        assert!(text.contains("[day_07.alice]\npart_1 = -1_234_567\npart_2 = 942_298\n"));
+
+        let text = with_answer(
+            &text,
+            10,
+            2,
+            "input",
+            &Answer::Text(String::from("#..#\n.##.\n")),
+        );
+        assert!(text.ends_with("\n\n[day_10.input]\npart_2 = \"\"\"\n#..#\n.##.\n\"\"\"\n"));
+        let text = with_answer(&text, 10, 1, "input", &Answer::Integer(100));
+        assert!(
+            text.ends_with("[day_10.input]\npart_1 = 100\npart_2 = \"\"\"\n#..#\n.##.\n\"\"\"\n")
+        );
+        let text = with_answer(
+            &text,
+            10,
+            2,
+            "input",
+            &Answer::Text(String::from("#..#\n#..#\n")),
+        );
+        assert!(
+            text.ends_with("[day_10.input]\npart_1 = 100\npart_2 = \"\"\"\n#..#\n#..#\n\"\"\"\n")
+        );
+
+        let manifest = Manifest::parse(&text).unwrap();
+        assert_eq!(
+            manifest.answer(5, 1, "input"),
+            Some(&Answer::Text(String::from("ZRLJGSCTR")))
+        );
+        assert_eq!(
+            manifest.answer(5, 2, "input"),
+            Some(&Answer::Text(String::from("PRTTGRFPB")))
+        );
+        assert_eq!(
+            manifest.answer(7, 2, "alice"),
+            Some(&Answer::Integer(942_298))
+        );
+        assert_eq!(
+            manifest.answer(10, 2, "input"),
+            Some(&Answer::Text(String::from("#..#\n#..#\n")))
+        );
+
+        assert_eq!(
+            with_answer("", 1, 2, "input", &Answer::Integer(45_000)),
+            "[day_01.input]\npart_2 = 45_000\n"
+        );
+    }
+
+    #[test]
+    fn test_checked_in_manifest() {
+        let manifest = Manifest::load(&Manifest::path()).unwrap();
+        assert_eq!(manifest.answer(2, 1, "sample"), Some(&Answer::Integer(15)));
+    }
+}
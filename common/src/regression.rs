@@ -0,0 +1,241 @@
+//! Regression tests against the inputs of every account, not just the one in `day_NN/input.txt`.
+//!
+//! Each day crate has a `tests/inputs.rs` that hands its solution to [`run`], which finds every
+//! `day_NN/inputs/<name>.txt` below [`input_dir`] and makes a test case of it with its known
+//! answers in `day_NN/answers/<name>.toml`:
+//!
+//! ```toml
+//! part_1 = 70_698
+//! part_2 = 206_643
+//! ```
+//!
+//! A part can be left out while its answer is unknown. An input without answers is reported as
+//! ignored, and answers without their input fail, so that a misspelt name does not go unnoticed.
+
+use crate::{
+    input::{input_dir, read_input},
+    manifest::InputAnswers,
+    Answer, ParseError, Solution,
+};
+use libtest_mimic::{Arguments, Trial};
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Solves the given part of a day, like [`Solution::try_solve`].
+pub type Solver = fn(u8, &str) -> Result<Answer, ParseError>;
+
+/// An input with its known answers, named after both files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub input: PathBuf,
+    pub answers: PathBuf,
+}
+
+/// Runs a test case for each input of the day `S` with the arguments of the test binary, and
+/// exits with its status.
+pub fn run<S: Solution>() -> ! {
+    let arguments = Arguments::from_args();
+    let day_dir = input_dir().join(format!("day_{:02}", S::DAY));
+    let trials = match find_cases(&day_dir) {
+        Ok(cases) => cases
+            .into_iter()
+            .map(|case| {
+                let ignored = !case.answers.exists();
+                Trial::test(case.name.clone(), move || {
+                    check(S::DAY, S::try_solve, &case).map_err(Into::into)
+                })
+                .with_ignored_flag(ignored)
+            })
+            .collect(),
+        Err(error) => {
+            let message = format!(
+                "could not list the inputs in {}: {error}",
+                day_dir.display()
+            );
+            vec![Trial::test("inputs", move || Err(message.into()))]
+        }
+    };
+    libtest_mimic::run(&arguments, trials).exit()
+}
+
+/// Finds the inputs in `day_dir/inputs` and the answers in `day_dir/answers`, pairing them by
+/// name. Either directory may be missing.
+///
+/// # Errors
+///
+/// Returns an error if one of the directories cannot be read.
+pub fn find_cases(day_dir: &Path) -> io::Result<Vec<Case>> {
+    let inputs = day_dir.join("inputs");
+    let answers = day_dir.join("answers");
+    let mut names = file_stems(&inputs, "txt")?;
+    names.extend(file_stems(&answers, "toml")?);
+    Ok(names
+        .into_iter()
+        .map(|name| Case {
+            input: inputs.join(format!("{name}.txt")),
+            answers: answers.join(format!("{name}.toml")),
+            name,
+        })
+        .collect())
+}
+
+/// The names of the files in `dir` with the given extension, in order.
+fn file_stems(dir: &Path, extension: &str) -> io::Result<BTreeSet<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(BTreeSet::new()),
+        Err(error) => return Err(error),
+    };
+    let mut names = BTreeSet::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|found| found == extension) {
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                names.insert(String::from(name));
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Solves both parts of the input of `case` and compares them with its answers.
+///
+/// # Errors
+///
+/// Returns a description of every part that does not match its answer, or of why the case could
+/// not be checked at all.
+pub fn check(day: u8, solve: Solver, case: &Case) -> Result<(), String> {
+    let answers = fs::read_to_string(&case.answers)
+        .map_err(|error| format!("could not read {}: {error}", case.answers.display()))?;
+    let answers: InputAnswers = toml::from_str(&answers)
+        .map_err(|error| format!("invalid answers in {}: {error}", case.answers.display()))?;
+    if answers.part_1.is_none() && answers.part_2.is_none() {
+        return Err(format!("no answers in {}", case.answers.display()));
+    }
+    let input = read_input(day, case.input.clone()).map_err(|error| error.to_string())?;
+
+    let failures: Vec<_> = [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let expected = answers.part(part)?;
+            match solve(part, &input) {
+                Ok(answer) if answer == *expected => None,
+                Ok(answer) if answer.is_multiline() || expected.is_multiline() => Some(format!(
+                    "part {part}: expected\n{expected}\nfound\n{answer}"
+                )),
+                Ok(answer) => Some(format!("part {part}: expected {expected}, found {answer}")),
+                Err(error) => Some(format!("part {part}: invalid input at {error}")),
+            }
+        })
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// Counts the lines for part 1 and joins them for part 2, rejecting digits in part 2.
+    fn solve(part: u8, input: &str) -> Result<Answer, ParseError> {
+        match (part, input.find(char::is_numeric)) {
+            (1, _) => Ok(Answer::from(input.lines().count())),
+            (_, Some(i)) => Err(ParseError::at_char(input, &input[i..], "a letter")),
+            (_, None) => Ok(Answer::Text(input.lines().collect())),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_find_cases() {
+        let dir = temp_dir("regression-find");
+        assert_eq!(find_cases(&dir).unwrap(), []);
+
+        write(&dir.join("inputs/bob.txt"), "");
+        write(&dir.join("inputs/alice.txt"), "");
+        write(&dir.join("inputs/notes.md"), "");
+        write(&dir.join("answers/alice.toml"), "");
+        write(&dir.join("answers/carol.toml"), "");
+        let cases = find_cases(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = cases.iter().map(|case| case.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob", "carol"]);
+        assert_eq!(cases[1].input, dir.join("inputs/bob.txt"));
+        assert_eq!(cases[1].answers, dir.join("answers/bob.toml"));
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = temp_dir("regression-check");
+        let case = |name: &str, input: &str, answers: &str| {
+            let case = Case {
+                name: String::from(name),
+                input: dir.join(format!("inputs/{name}.txt")),
+                answers: dir.join(format!("answers/{name}.toml")),
+            };
+            write(&case.input, input);
+            write(&case.answers, answers);
+            case
+        };
+
+        let pass = case("pass", "a\r\nb\n", "part_1 = 2\npart_2 = \"ab\"\n");
+        let partial = case("partial", "a\n1\n", "part_1 = 2\n");
+        let wrong = case("wrong", "a\n1\n", "part_1 = 3\npart_2 = \"a1\"\n");
+        let empty = case("empty", "a\n", "");
+        let invalid = case("invalid", "a\n", "part_3 = 1\n");
+        let results = [&pass, &partial, &wrong, &empty, &invalid].map(|case| check(1, solve, case));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(results[0], Ok(()));
+        assert_eq!(results[1], Ok(()));
+        assert_eq!(
+            results[2].as_ref().map_err(String::as_str),
+            Err(concat!(
+                "part 1: expected 3, found 2\n",
+                "part 2: invalid input at line 2, column 1: expected a letter, found `1`\n",
+                "  |\n2 | 1\n  | ^",
+            ))
+        );
+        assert!(results[3]
+            .as_ref()
+            .unwrap_err()
+            .starts_with("no answers in "));
+        assert!(results[4]
+            .as_ref()
+            .unwrap_err()
+            .starts_with("invalid answers in "));
+    }
+
+    #[test]
+    fn test_check_missing_input() {
+        let dir = temp_dir("regression-missing");
+        let case = Case {
+            name: String::from("carol"),
+            input: dir.join("inputs/carol.txt"),
+            answers: dir.join("answers/carol.toml"),
+        };
+        write(&case.answers, "part_1 = 1\n");
+        let result = check(1, solve, &case);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(result.unwrap_err().contains("inputs/carol.txt"));
+    }
+}
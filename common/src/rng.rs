@@ -0,0 +1,80 @@
+use std::ops::RangeInclusive;
+
+/// A small pseudorandom number generator (`SplitMix64`) for generating puzzle inputs.
+///
+/// It is not suitable for anything that needs good randomness, but the same seed gives the same
+/// numbers on every platform and in every version, which keeps generated inputs reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range, which must not be empty.
+    pub fn range<T>(&mut self, range: RangeInclusive<T>) -> T
+    where
+        T: TryInto<i128> + TryFrom<i128>,
+    {
+        let (start, end) = range.into_inner();
+        let start: i128 = start.try_into().ok().unwrap();
+        let end: i128 = end.try_into().ok().unwrap();
+        assert!(start <= end, "empty range {start}..={end}");
+        let offset = i128::from(self.next_u64()) % (end - start + 1);
+        T::try_from(start + offset).ok().unwrap()
+    }
+
+    /// Whether an event with the given chance in percent happens.
+    pub fn percent(&mut self, chance: u64) -> bool {
+        self.next_u64() % 100 < chance
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    /// Shuffles the items in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let numbers: Vec<u8> = (0..8).map(|_| Rng::new(7).range(1..=6)).collect();
+        assert!(numbers.iter().all(|&number| number == numbers[0]));
+
+        let mut rng = Rng::new(7);
+        let mut numbers: Vec<i32> = (0..1000).map(|_| rng.range(-3..=3)).collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        assert_eq!(numbers, [-3, -2, -1, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut items: Vec<_> = (0..20).collect();
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
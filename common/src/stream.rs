@@ -0,0 +1,399 @@
+use crate::ParseError;
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{Display, Formatter, Result as FormatResult},
+    io::{self, BufRead},
+    iter::from_fn,
+    mem::swap,
+    str,
+};
+
+/// Why a puzzle input streamed from a reader could not be solved.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            Self::Io(error) => write!(formatter, "could not read the input: {error}"),
+            Self::Parse(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+/// Reads a puzzle input one line at a time, keeping only the current line in memory.
+///
+/// Each line is parsed as if it were the whole input, so the parsers of the solutions can be
+/// reused as they are; the errors they return are then moved to the line in the whole input.
+/// Like [`crate::input::normalize`], this drops whitespace at the end of lines and blank lines
+/// at the end of the input.
+pub struct LineReader<R> {
+    reader: R,
+    /// The current line, with its line ending.
+    line: String,
+    /// The buffer the next line is read into, which becomes the current line unless the end of
+    /// the input was reached.
+    next_line: String,
+    /// Blank lines that were read to find out whether anything follows them, and have yet to be
+    /// parsed.
+    blank_lines: usize,
+    /// The line that was found after those blank lines.
+    line_after_blank_lines: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            next_line: String::new(),
+            blank_lines: 0,
+            line_after_blank_lines: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// Reads the next line and parses it without its line ending, or returns `None` at the end
+    /// of the input.
+    pub fn parse_line<T>(
+        &mut self,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Option<Result<T, ReadError>> {
+        match self.next_line() {
+            Ok(true) => (),
+            Ok(false) => return None,
+            Err(error) => return Some(Err(ReadError::Io(error))),
+        }
+        self.line_number += 1;
+        Some(parse(self.line.trim_end()).map_err(|error| self.locate(error)))
+    }
+
+    /// Makes the next line the current one, or returns `false` if only blank lines are left.
+    fn next_line(&mut self) -> io::Result<bool> {
+        if self.blank_lines > 0 {
+            self.blank_lines -= 1;
+            self.line.clear();
+            self.line.push('\n');
+            return Ok(true);
+        }
+        if !self.line_after_blank_lines.is_empty() {
+            swap(&mut self.line, &mut self.line_after_blank_lines);
+            self.line_after_blank_lines.clear();
+            return Ok(true);
+        }
+
+        self.next_line.clear();
+        if self.reader.read_line(&mut self.next_line)? == 0 {
+            return Ok(false);
+        }
+        if self.next_line.trim_end().is_empty() {
+            loop {
+                self.blank_lines += 1;
+                if self.reader.read_line(&mut self.line_after_blank_lines)? == 0 {
+                    // Blank lines at the end of the input are dropped.
+                    self.blank_lines = 0;
+                    return Ok(false);
+                }
+                if !self.line_after_blank_lines.trim_end().is_empty() {
+                    break;
+                }
+                self.line_after_blank_lines.clear();
+            }
+            return self.next_line();
+        }
+        swap(&mut self.line, &mut self.next_line);
+        Ok(true)
+    }
+
+    /// Parses the remaining lines, stopping after the first error.
+    pub fn parse_lines<'r, T: 'r>(
+        &'r mut self,
+        mut parse: impl FnMut(&str) -> Result<T, ParseError> + 'r,
+    ) -> impl Iterator<Item = Result<T, ReadError>> + 'r {
+        let mut failed = false;
+        from_fn(move || {
+            if failed {
+                return None;
+            }
+            let result = self.parse_line(&mut parse)?;
+            failed = result.is_err();
+            Some(result)
+        })
+    }
+
+    /// Reports that the input ended where something else was `expected`.
+    pub fn end_of_input(&self, expected: impl Into<String>) -> ReadError {
+        let end = &self.line[self.line.len()..];
+        let mut error = ParseError::at(&self.line, end, expected);
+        error.line += self.line_number.saturating_sub(1);
+        ReadError::Parse(error)
+    }
+
+    /// Moves an error in the current line, which was parsed on its own, to its place in the whole
+    /// input. The end of the line was then the end of the input, so it is reported as such.
+    fn locate(&self, mut error: ParseError) -> ReadError {
+        error.line += self.line_number - 1;
+        if error.found == "end of input" && self.line.ends_with('\n') {
+            error.found = String::from("end of line");
+        }
+        ReadError::Parse(error)
+    }
+}
+
+/// Reads a puzzle input one byte at a time as [`crate::input::normalize`] leaves it, for inputs
+/// whose lines are too long to keep in memory.
+///
+/// Only what may turn out to be at the end of a line or of the input is held back: the line
+/// endings since the last character that is not whitespace, as a count, and the whitespace since
+/// then on the current line.
+pub struct NormalizedBytes<R> {
+    reader: R,
+    /// The bytes of the character being read.
+    char_bytes: [u8; 4],
+    char_len: usize,
+    line_endings: usize,
+    whitespace: Vec<u8>,
+    /// Whether anything other than whitespace was read at all.
+    has_content: bool,
+    /// Line endings that are kept, to hand out before `ready`.
+    ready_line_endings: usize,
+    ready: VecDeque<u8>,
+    ended: bool,
+}
+
+impl<R: BufRead> NormalizedBytes<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            char_bytes: [0; 4],
+            char_len: 0,
+            line_endings: 0,
+            whitespace: Vec::new(),
+            has_content: false,
+            ready_line_endings: 0,
+            ready: VecDeque::new(),
+            ended: false,
+        }
+    }
+
+    /// Reads the next character, or returns `false` at the end of the input.
+    fn read_char(&mut self) -> io::Result<bool> {
+        loop {
+            let Some(&byte) = self.reader.fill_buf()?.first() else {
+                if self.char_len > 0 {
+                    return Err(invalid_utf8());
+                }
+                return Ok(false);
+            };
+            self.reader.consume(1);
+            self.char_bytes[self.char_len] = byte;
+            self.char_len += 1;
+            let char_len = match self.char_bytes[0] {
+                0x00..=0x7F => 1,
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => return Err(invalid_utf8()),
+            };
+            if self.char_len == char_len {
+                break;
+            }
+        }
+        let bytes = &self.char_bytes[..self.char_len];
+        self.char_len = 0;
+        let character = str::from_utf8(bytes).map_err(|_| invalid_utf8())?;
+        if character == "\n" {
+            self.line_endings += 1;
+            self.whitespace.clear();
+        } else if character.chars().all(char::is_whitespace) {
+            self.whitespace.extend_from_slice(bytes);
+        } else {
+            self.ready_line_endings = self.line_endings;
+            self.line_endings = 0;
+            self.ready.extend(self.whitespace.drain(..));
+            self.ready.extend(bytes);
+            self.has_content = true;
+        }
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Iterator for NormalizedBytes<R> {
+    type Item = io::Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.ready_line_endings == 0 && self.ready.is_empty() && !self.ended {
+            match self.read_char() {
+                Ok(true) => (),
+                Ok(false) => {
+                    // A single line ending is kept after the last line.
+                    self.ended = true;
+                    if self.has_content && self.line_endings > 0 {
+                        self.ready_line_endings = 1;
+                    }
+                }
+                Err(error) => {
+                    self.ended = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+        if self.ready_line_endings > 0 {
+            self.ready_line_endings -= 1;
+            return Some(Ok(b'\n'));
+        }
+        self.ready.pop_front().map(Ok)
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "the input is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_number(line: &str) -> Result<u32, ParseError> {
+        line.parse()
+            .map_err(|_| ParseError::at(line, line, "a number"))
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let mut reader = LineReader::new("1\r\n2\n\n3".as_bytes());
+        let numbers: Vec<_> = reader
+            .parse_lines(|line| Ok(parse_number(line).ok()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(numbers, [Some(1), Some(2), None, Some(3)]);
+    }
+
+    #[test]
+    fn test_whitespace() {
+        let mut reader = LineReader::new("1 \r\n\r\n \n2\t\n\n\r\n".as_bytes());
+        let numbers: Vec<_> = reader
+            .parse_lines(|line| Ok(parse_number(line).ok()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(numbers, [Some(1), None, None, Some(2)]);
+        let ReadError::Parse(error) = reader.end_of_input("more") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (5, 1));
+    }
+
+    #[test]
+    fn test_after_end() {
+        let mut reader = LineReader::new("1\n\n\n".as_bytes());
+        let mut parse = || {
+            reader
+                .parse_line(|line| Ok(line.to_owned()))
+                .map(Result::unwrap)
+        };
+        assert_eq!(parse().as_deref(), Some("1"));
+        assert_eq!(parse(), None);
+        assert_eq!(parse(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut reader = LineReader::new("1\n2\nx3\n4\n".as_bytes());
+        let results: Vec<_> = reader.parse_lines(parse_number).collect();
+        assert_eq!(results.len(), 3);
+        let Some(Err(ReadError::Parse(error))) = results.last() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.found, "`x3`");
+
+        let mut reader = LineReader::new("1\n".as_bytes());
+        let error = reader
+            .parse_line(|line| Err::<(), _>(ParseError::at(line, &line[1..], "a space")))
+            .unwrap()
+            .unwrap_err();
+        let ReadError::Parse(error) = error else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(error.found, "end of line");
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        for (input, position, line_text) in
+            [("", (1, 1), ""), ("1\n2", (2, 2), "2"), ("1\n", (2, 1), "")]
+        {
+            let mut reader = LineReader::new(input.as_bytes());
+            reader.parse_lines(parse_number).for_each(drop);
+            let ReadError::Parse(error) = reader.end_of_input("more") else {
+                panic!("expected a parse error");
+            };
+            assert_eq!((error.line, error.column), position);
+            assert_eq!(error.line_text, line_text);
+            assert_eq!(error.found, "end of input");
+        }
+    }
+
+    #[test]
+    fn test_normalized_bytes() {
+        use crate::input::normalize;
+
+        for input in [
+            "",
+            "\n",
+            "abc",
+            "abc \r\n",
+            "a b\t\n \n\r\nc\u{3000}d\u{a0}\n\n \n",
+            "\n\n  x\n",
+            " \t\n",
+        ] {
+            let bytes: Vec<_> = NormalizedBytes::new(input.as_bytes())
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(bytes, normalize(input).as_bytes(), "{input:?}");
+        }
+
+        let mut bytes = NormalizedBytes::new(&b"ab\xe2\x80"[..]);
+        assert_eq!(bytes.next().unwrap().unwrap(), b'a');
+        assert_eq!(bytes.next().unwrap().unwrap(), b'b');
+        assert!(bytes.next().unwrap().is_err());
+        assert!(bytes.next().is_none());
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut reader = LineReader::new(&b"1\n\xff\n"[..]);
+        let error = reader
+            .parse_lines(parse_number)
+            .last()
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(error, ReadError::Io(_)));
+    }
+}
@@ -0,0 +1,214 @@
+//! Structured events that the solutions emit as they go, to follow a simulation step by step when
+//! an answer is off.
+//!
+//! The solutions emit events with the [`trace!`](crate::trace!) macro, which does nothing unless
+//! a sink was installed on the thread with [`with_sink`] or [`collect`]. Without the `trace`
+//! feature the macro compiles to nothing at all.
+
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result as FormatResult},
+    rc::Rc,
+};
+
+/// The value of a field of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Signed(i64),
+    Unsigned(u64),
+    Bool(bool),
+    Text(String),
+}
+
+macro_rules! impl_from {
+    ($variant:ident($type:ty): $($from:ty),*) => {
+        $(
+            impl From<$from> for Value {
+                fn from(value: $from) -> Self {
+                    Self::$variant(<$type>::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Signed(i64): i8, i16, i32, i64);
+impl_from!(Unsigned(u64): u8, u16, u32, u64);
+impl_from!(Bool(bool): bool);
+impl_from!(Text(String): char, &str, String);
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+
+impl From<Ordering> for Value {
+    fn from(ordering: Ordering) -> Self {
+        let text = match ordering {
+            Ordering::Less => "less",
+            Ordering::Equal => "equal",
+            Ordering::Greater => "greater",
+        };
+        Self::Text(String::from(text))
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            Self::Signed(value) => write!(formatter, "{value}"),
+            Self::Unsigned(value) => write!(formatter, "{value}"),
+            Self::Bool(value) => write!(formatter, "{value}"),
+            Self::Text(text) => write!(formatter, "{text:?}"),
+        }
+    }
+}
+
+/// Something that happened in a solution, like a move or a comparison, with the fields that
+/// describe it in the order they were given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+impl Event {
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find_map(|(field, value)| (*field == name).then_some(value))
+    }
+}
+
+/// Shows the event as its name followed by `field=value` pairs.
+impl Display for Event {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        write!(formatter, "{}", self.name)?;
+        for (field, value) in &self.fields {
+            write!(formatter, " {field}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+type Sink = Box<dyn FnMut(Event)>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Whether events emitted on this thread go anywhere. The macro checks this before it builds an
+/// event.
+#[must_use]
+pub fn enabled() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+/// Passes an event to the sink of this thread, if there is one.
+pub fn emit(event: Event) {
+    SINK.with(|sink| {
+        if let Some(sink) = sink.borrow_mut().as_mut() {
+            sink(event);
+        }
+    });
+}
+
+/// Runs `f` with every event it emits on this thread passed to `sink`, and then puts back the
+/// sink that was there before, even if `f` panics. The sink must not emit events itself.
+pub fn with_sink<T>(sink: impl FnMut(Event) + 'static, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Sink>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SINK.with(|sink| *sink.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SINK.with(|current| current.replace(Some(Box::new(sink))));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Runs `f` and returns the events it emitted on this thread along with its result.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = Rc::clone(&events);
+    let result = with_sink(move |event| sink.borrow_mut().push(event), f);
+    let events = events.take();
+    (result, events)
+}
+
+/// Emits an event with a name and `field = value` pairs, where the values convert into
+/// [`Value`]s. The values are only evaluated if a sink is listening.
+///
+/// ```
+/// let (_, events) = common::trace::collect(|| common::trace!("move", from = 1, to = 'b'));
+/// assert_eq!(events[0].to_string(), r#"move from=1 to="b""#);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit($crate::trace::Event {
+                name: $name,
+                fields: ::std::vec![
+                    $((::std::stringify!($field), $crate::trace::Value::from($value))),*
+                ],
+            });
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        let (result, events) = collect(|| {
+            crate::trace!("start");
+            crate::trace!("move", knot = 3_usize, x = -2, done = false);
+            7
+        });
+        assert_eq!(result, 7);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].to_string(), "start");
+        assert_eq!(events[1].to_string(), "move knot=3 x=-2 done=false");
+        assert_eq!(events[1].field("x"), Some(&Value::Signed(-2)));
+        assert_eq!(events[1].field("y"), None);
+    }
+
+    #[test]
+    fn test_no_sink() {
+        assert!(!enabled());
+        let mut evaluated = false;
+        crate::trace!(
+            "skipped",
+            value = {
+                evaluated = true;
+                1
+            }
+        );
+        assert!(!evaluated);
+    }
+
+    #[test]
+    fn test_nested_sinks() {
+        let (((), inner), outer) = collect(|| {
+            crate::trace!("outer");
+            let inner = collect(|| crate::trace!("inner"));
+            crate::trace!("outer", order = Ordering::Less, crates = "CM");
+            inner
+        });
+        assert_eq!(inner.len(), 1);
+        assert_eq!(
+            outer.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["outer", r#"outer order="less" crates="CM""#]
+        );
+        assert!(!enabled());
+    }
+}
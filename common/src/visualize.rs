@@ -0,0 +1,355 @@
+use crate::{ParseError, Solution};
+use std::fmt::Write;
+
+/// The colours a cell can be drawn in. Renderers without colours ignore them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Color {
+    #[default]
+    Default,
+    /// For what is in the background, like cells that have been dealt with.
+    Dim,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            glyph: ' ',
+            color: Color::Default,
+        }
+    }
+}
+
+/// A snapshot of the state of a solution: a grid of coloured characters, with a caption below
+/// it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    num_rows: usize,
+    num_columns: usize,
+    cells: Vec<Cell>,
+    pub caption: String,
+}
+
+impl Frame {
+    /// Creates a frame of blank cells.
+    #[must_use]
+    pub fn new(num_rows: usize, num_columns: usize) -> Self {
+        Self {
+            num_rows,
+            num_columns,
+            cells: vec![Cell::default(); num_rows * num_columns],
+            caption: String::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    #[must_use]
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> Cell {
+        self.cells[self.index(row, column)]
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, glyph: char, color: Color) {
+        let index = self.index(row, column);
+        self.cells[index] = Cell { glyph, color };
+    }
+
+    /// Writes `text` from the given cell to the right, clipped at the edge of the frame.
+    pub fn write(&mut self, row: usize, column: usize, text: &str, color: Color) {
+        for (column, glyph) in (column..self.num_columns).zip(text.chars()) {
+            self.set(row, column, glyph, color);
+        }
+    }
+
+    /// The cells of each row, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.num_columns.max(1))
+    }
+
+    fn index(&self, row: usize, column: usize) -> usize {
+        assert!(
+            row < self.num_rows && column < self.num_columns,
+            "cell ({row}, {column}) outside a frame of {} by {}",
+            self.num_rows,
+            self.num_columns
+        );
+        row * self.num_columns + column
+    }
+}
+
+/// Turns frames into text in some format, to print or to write to files.
+pub trait Renderer {
+    /// The extension of the files the frames are written to.
+    fn extension(&self) -> &'static str;
+
+    fn render(&self, frame: &Frame) -> String;
+}
+
+/// Plain text, without colours.
+pub struct Ascii;
+
+impl Renderer for Ascii {
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn render(&self, frame: &Frame) -> String {
+        let mut text = String::new();
+        for row in frame.rows() {
+            text.extend(trim_end(row).iter().map(|cell| cell.glyph));
+            text.push('\n');
+        }
+        text.push_str(&frame.caption);
+        text.push('\n');
+        text
+    }
+}
+
+/// Text with ANSI escape codes for the colours, for a terminal.
+pub struct Ansi;
+
+impl Ansi {
+    fn escape_code(color: Color) -> &'static str {
+        match color {
+            Color::Default => "\x1b[0m",
+            Color::Dim => "\x1b[0;2m",
+            Color::Red => "\x1b[0;31m",
+            Color::Green => "\x1b[0;32m",
+            Color::Yellow => "\x1b[0;33m",
+            Color::Blue => "\x1b[0;34m",
+            Color::Magenta => "\x1b[0;35m",
+            Color::Cyan => "\x1b[0;36m",
+        }
+    }
+}
+
+impl Renderer for Ansi {
+    fn extension(&self) -> &'static str {
+        "ans"
+    }
+
+    fn render(&self, frame: &Frame) -> String {
+        let mut text = String::new();
+        for row in frame.rows() {
+            let mut color = Color::Default;
+            for cell in trim_end(row) {
+                // Spaces look the same in any colour, so they do not need a switch.
+                if cell.color != color && cell.glyph != ' ' {
+                    color = cell.color;
+                    text.push_str(Self::escape_code(color));
+                }
+                text.push(cell.glyph);
+            }
+            if color != Color::Default {
+                text.push_str(Self::escape_code(Color::Default));
+            }
+            text.push('\n');
+        }
+        text.push_str(&frame.caption);
+        text.push('\n');
+        text
+    }
+}
+
+/// A standalone SVG image, with light text on a dark background.
+pub struct Svg {
+    pub cell_width: usize,
+    pub cell_height: usize,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self {
+            cell_width: 9,
+            cell_height: 16,
+        }
+    }
+}
+
+impl Svg {
+    fn fill(color: Color) -> &'static str {
+        match color {
+            Color::Default => "#cccccc",
+            Color::Dim => "#555566",
+            Color::Red => "#ff5555",
+            Color::Green => "#50fa7b",
+            Color::Yellow => "#f1fa8c",
+            Color::Blue => "#6272ff",
+            Color::Magenta => "#ff79c6",
+            Color::Cyan => "#8be9fd",
+        }
+    }
+
+    /// Writes a run of glyphs of one colour as a single text element, with the position of every
+    /// glyph given, so that they line up with the grid whatever the font.
+    fn write_run(&self, svg: &mut String, row: usize, run: &[(usize, Cell)]) {
+        let Some(&(_, first)) = run.first() else {
+            return;
+        };
+        let xs: Vec<String> = run
+            .iter()
+            .map(|&(column, _)| (column * self.cell_width).to_string())
+            .collect();
+        let glyphs: String = run.iter().map(|(_, cell)| cell.glyph).collect();
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+            xs.join(" "),
+            (row + 1) * self.cell_height - 4,
+            Self::fill(first.color),
+            escape_xml(&glyphs)
+        )
+        .unwrap();
+    }
+}
+
+impl Renderer for Svg {
+    fn extension(&self) -> &'static str {
+        "svg"
+    }
+
+    fn render(&self, frame: &Frame) -> String {
+        let width = frame.num_columns().max(frame.caption.chars().count()) * self.cell_width;
+        let height = (frame.num_rows() + 1) * self.cell_height;
+        let mut svg = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="monospace" font-size="{}" xml:space="preserve">
+<rect width="100%" height="100%" fill="#0f0f23"/>
+"##,
+            self.cell_height - 2
+        );
+        for (row, cells) in frame.rows().enumerate() {
+            let mut run: Vec<(usize, Cell)> = Vec::new();
+            for (column, &cell) in cells.iter().enumerate() {
+                if cell.glyph == ' ' {
+                    continue;
+                }
+                if run.last().is_some_and(|(_, last)| last.color != cell.color) {
+                    self.write_run(&mut svg, row, &run);
+                    run.clear();
+                }
+                run.push((column, cell));
+            }
+            self.write_run(&mut svg, row, &run);
+        }
+        let caption: Vec<_> = frame
+            .caption
+            .chars()
+            .map(|glyph| Cell {
+                glyph,
+                color: Color::Dim,
+            })
+            .enumerate()
+            .collect();
+        self.write_run(&mut svg, frame.num_rows(), &caption);
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// The cells of a row up to the last one that is not blank.
+fn trim_end(row: &[Cell]) -> &[Cell] {
+    let len = row
+        .iter()
+        .rposition(|cell| cell.glyph != ' ')
+        .map_or(0, |i| i + 1);
+    &row[..len]
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A solution that can show how it gets to its answers, as a sequence of frames.
+pub trait Visualize: Solution {
+    /// The frames for the given part (1 or 2), from the start to the answer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the first part of the input that does not match the puzzle.
+    fn frames(part: u8, input: &str) -> Result<Vec<Frame>, ParseError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(2, 4).with_caption("step 1");
+        frame.write(0, 0, "ab", Color::Red);
+        frame.set(0, 3, '<', Color::Default);
+        frame.write(1, 1, "&xyz", Color::Dim);
+        frame
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = frame();
+        assert_eq!((frame.num_rows(), frame.num_columns()), (2, 4));
+        assert_eq!(
+            frame.get(1, 3),
+            Cell {
+                glyph: 'y',
+                color: Color::Dim
+            }
+        );
+        assert_eq!(frame.get(0, 2), Cell::default());
+    }
+
+    #[test]
+    #[should_panic = "cell (2, 0) outside a frame of 2 by 4"]
+    fn test_outside_frame() {
+        Frame::new(2, 4).set(2, 0, 'x', Color::Default);
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!(Ascii.render(&frame()), "ab <\n &xy\nstep 1\n");
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(
+            Ansi.render(&frame()),
+            "\x1b[0;31mab \x1b[0m<\n \x1b[0;2m&xy\x1b[0m\nstep 1\n"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = Svg::default().render(&frame());
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="54" height="48""#)
+        );
+        assert!(svg.contains(r##"<text x="0 9" y="12" fill="#ff5555">ab</text>"##));
+        assert!(svg.contains(r##"<text x="9 18 27" y="28" fill="#555566">&amp;xy</text>"##));
+        assert!(svg.contains(r##"<text x="0 9 18 27 36 45" y="44" fill="#555566">step 1</text>"##));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -1,13 +1,85 @@
-use criterion::{criterion_group, criterion_main, Criterion};
-use day_01::{part_1, part_2};
+use common::input;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day_01::{calories_per_elf_scalar, generator, part_1, part_2, swar, top_n_calories};
+
+/// The real puzzle input, if it is there. Without it, only the generated inputs are measured.
+fn load_input() -> Option<String> {
+    input::load_input(1)
+        .map_err(|error| eprintln!("skipping benchmark: {error}"))
+        .ok()
+}
 
 fn part_1_benchmark(c: &mut Criterion) {
-    c.bench_function("part_1", |b| b.iter(part_1));
+    let Some(input) = load_input() else { return };
+    c.bench_function("part_1", |b| b.iter(|| part_1(black_box(&input))));
 }
 
 fn part_2_benchmark(c: &mut Criterion) {
-    c.bench_function("part_2", |b| b.iter(part_2));
+    let Some(input) = load_input() else { return };
+    c.bench_function("part_2", |b| b.iter(|| part_2(black_box(&input))));
+}
+
+/// The approximate sizes in bytes of the generated inputs, to measure how the solution scales.
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+fn scaling_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scaling");
+    group.sample_size(10);
+    for size in SIZES {
+        let input = generator::generate(size, 2022);
+        group.throughput(Throughput::Bytes(u64::try_from(input.len()).unwrap()));
+        group.bench_with_input(BenchmarkId::new("part_1", size), &input, |b, input| {
+            b.iter(|| part_1(black_box(input)));
+        });
+        group.bench_with_input(BenchmarkId::new("part_2", size), &input, |b, input| {
+            b.iter(|| part_2(black_box(input)));
+        });
+    }
+    group.finish();
+}
+
+/// The approximate sizes in bytes of the large inputs, to compare selecting the largest totals
+/// with a heap against sorting all of them.
+const LARGE_SIZES: [usize; 2] = [1_000_000, 10_000_000];
+
+fn top_n_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("top_n");
+    group.sample_size(10);
+    for size in LARGE_SIZES {
+        let input = generator::generate(size, 2022);
+        group.throughput(Throughput::Bytes(u64::try_from(input.len()).unwrap()));
+        group.bench_with_input(BenchmarkId::new("sort", size), &input, |b, input| {
+            b.iter(|| part_2(black_box(input)));
+        });
+        group.bench_with_input(BenchmarkId::new("heap", size), &input, |b, input| {
+            b.iter(|| top_n_calories(black_box(input), 3));
+        });
+    }
+    group.finish();
+}
+
+fn parse_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    group.sample_size(10);
+    for size in LARGE_SIZES {
+        let input = generator::generate(size, 2022);
+        group.throughput(Throughput::Bytes(u64::try_from(input.len()).unwrap()));
+        group.bench_with_input(BenchmarkId::new("scalar", size), &input, |b, input| {
+            b.iter(|| calories_per_elf_scalar(black_box(input)));
+        });
+        group.bench_with_input(BenchmarkId::new("swar", size), &input, |b, input| {
+            b.iter(|| swar::calories_per_elf(black_box(input)));
+        });
+    }
+    group.finish();
 }
 
-criterion_group!(benches, part_1_benchmark, part_2_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_group!(
+    benches,
+    part_1_benchmark,
+    part_2_benchmark,
+    scaling_benchmark,
+    top_n_benchmark,
+    parse_benchmark
+);
+criterion_main!(benches);
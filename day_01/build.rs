@@ -0,0 +1,42 @@
+//! Works out the answers for `input.txt` while the crate is built with the `embedded-input`
+//! feature, so that the `precomputed` feature can return them without parsing the input at all.
+//!
+//! This is a plain implementation of its own rather than the one in the crate, which a build
+//! script cannot use, so the tests comparing the two are a second check of the logic.
+
+#![deny(clippy::all, clippy::pedantic)]
+
+use std::{env, fs, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=input.txt");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_INPUT").is_none() {
+        return;
+    }
+    let input = fs::read_to_string("input.txt")
+        .unwrap_or_else(|error| panic!("could not read input.txt: {error}"));
+
+    let mut totals: Vec<u32> = input
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|elf| {
+            elf.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    line.parse::<u32>().unwrap_or_else(|error| {
+                        panic!("invalid number of calories {line:?} in input.txt: {error}")
+                    })
+                })
+                .sum()
+        })
+        .collect();
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    let part_1 = totals[0];
+    let part_2: u32 = totals.iter().take(3).sum();
+
+    let path = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("answers.rs");
+    let answers = format!("pub const PART_1: u32 = {part_1};\npub const PART_2: u32 = {part_2};\n");
+    fs::write(&path, answers)
+        .unwrap_or_else(|error| panic!("could not write {}: {error}", path.display()));
+}
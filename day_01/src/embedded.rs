@@ -0,0 +1,51 @@
+//! The puzzle input in `input.txt`, built into the crate with the `embedded-input` feature so that
+//! its answers need no file at run time.
+
+/// The contents of `input.txt` when the crate was built.
+pub const INPUT: &str = include_str!("../input.txt");
+
+/// The answers for [`INPUT`], worked out by `build.rs` when the crate is built.
+#[allow(clippy::unreadable_literal)]
+pub mod precomputed {
+    include!(concat!(env!("OUT_DIR"), "/answers.rs"));
+}
+
+/// Solves part 1 for [`INPUT`], or returns [`precomputed::PART_1`] with the `precomputed`
+/// feature.
+#[must_use]
+pub fn part_1() -> u32 {
+    if cfg!(feature = "precomputed") {
+        precomputed::PART_1
+    } else {
+        crate::part_1(INPUT)
+    }
+}
+
+/// Solves part 2 for [`INPUT`], or returns [`precomputed::PART_2`] with the `precomputed`
+/// feature.
+#[must_use]
+pub fn part_2() -> u32 {
+    if cfg!(feature = "precomputed") {
+        precomputed::PART_2
+    } else {
+        crate::part_2(INPUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{manifest::expected_answer, Answer};
+
+    #[test]
+    fn test_embedded() {
+        assert_eq!(Answer::from(part_1()), expected_answer(1, 1, "input"));
+        assert_eq!(Answer::from(part_2()), expected_answer(1, 2, "input"));
+    }
+
+    #[test]
+    fn test_precomputed() {
+        assert_eq!(precomputed::PART_1, crate::part_1(INPUT));
+        assert_eq!(precomputed::PART_2, crate::part_2(INPUT));
+    }
+}
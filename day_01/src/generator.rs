@@ -0,0 +1,36 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+/// Generates about `size` bytes of calorie counts, in blocks of one to ten items per Elf.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 64);
+    let mut num_elves = 0;
+    while input.len() < size || num_elves < 3 {
+        if num_elves > 0 {
+            input.push('\n');
+        }
+        for _ in 0..rng.range(1..=10) {
+            writeln!(input, "{}", rng.range(1_000..=60_000)).unwrap();
+        }
+        num_elves += 1;
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day01;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((10_000..10_100).contains(&input.len()));
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day01::try_part_2(&input).is_ok());
+        assert!(Day01::try_part_1(&generate(0, 2)).is_ok());
+    }
+}
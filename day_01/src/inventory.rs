@@ -0,0 +1,328 @@
+use crate::parse_calories;
+use common::{input::normalize, ParseError};
+use std::cmp::Reverse;
+
+/// One Elf and the calories of each item in its pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    index: usize,
+    items: Vec<u32>,
+    total: u32,
+}
+
+impl Elf {
+    /// The position of the Elf in the input, from 0.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[must_use]
+    pub fn items(&self) -> &[u32] {
+        &self.items
+    }
+
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+}
+
+/// An Elf with its place in the ranking by total calories, where Elves with the same total share
+/// a rank and the next rank is skipped for each of them, as in 1, 2, 2, 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranked<'a> {
+    pub rank: usize,
+    pub elf: &'a Elf,
+}
+
+/// What every Elf carries, keeping track of who carries what rather than just the totals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+}
+
+impl Inventory {
+    /// Reads the packs of the Elves, which are separated by blank lines. The input is split into
+    /// Elves like the solution splits it, at every two line endings in a row, so two additional
+    /// blank lines between Elves make an Elf with an empty pack and a single one is ignored. The
+    /// input is normalized first, like for the parts of the puzzle, so line endings and blank
+    /// lines at the end make no difference.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an item is not a number, or if an item or a total does not fit in 32
+    /// bits.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let input = &*normalize(input);
+        let mut elves = Vec::new();
+        for pack in input.split("\n\n") {
+            let mut items = Vec::new();
+            let mut total = 0_u32;
+            for line in pack.lines().filter(|line| !line.is_empty()) {
+                let calories = parse_calories(input, line)?;
+                total = total.checked_add(calories).ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        line,
+                        "a total number of calories that fits in 32 bits",
+                    )
+                })?;
+                items.push(calories);
+            }
+            elves.push(Elf {
+                index: elves.len(),
+                items,
+                total,
+            });
+        }
+        Ok(Self { elves })
+    }
+
+    /// The Elves in the order of the input.
+    #[must_use]
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// Every Elf from the most calories to the fewest, and in the order of the input for the same
+    /// total.
+    #[must_use]
+    pub fn ranking(&self) -> Vec<Ranked<'_>> {
+        let mut elves: Vec<_> = self.elves.iter().collect();
+        elves.sort_by_key(|elf| (Reverse(elf.total), elf.index));
+        let mut ranking: Vec<Ranked<'_>> = Vec::with_capacity(elves.len());
+        for (i, elf) in elves.into_iter().enumerate() {
+            let rank = match ranking.last() {
+                Some(previous) if previous.elf.total == elf.total => previous.rank,
+                _ => i + 1,
+            };
+            ranking.push(Ranked { rank, elf });
+        }
+        ranking
+    }
+
+    /// The Elves ranked `n` or better. This is more than `n` Elves when others have the same
+    /// total as the last of them, so that none of the heavy carriers is left out arbitrarily.
+    #[must_use]
+    pub fn top(&self, n: usize) -> Vec<Ranked<'_>> {
+        let mut ranking = self.ranking();
+        ranking.retain(|ranked| ranked.rank <= n);
+        ranking
+    }
+
+    /// The groups of Elves that carry the same total, from the largest total, with the Elves of
+    /// each group in the order of the input.
+    #[must_use]
+    pub fn ties(&self) -> Vec<Vec<&Elf>> {
+        let ranking = self.ranking();
+        ranking
+            .chunk_by(|a, b| a.rank == b.rank)
+            .filter(|group| group.len() > 1)
+            .map(|group| group.iter().map(|ranked| ranked.elf).collect())
+            .collect()
+    }
+
+    /// The middle total, or the mean of the two middle ones for an even number of Elves.
+    #[must_use]
+    pub fn median(&self) -> Option<f64> {
+        let mut totals: Vec<_> = self.elves.iter().map(Elf::total).collect();
+        totals.sort_unstable();
+        let middle = totals.len() / 2;
+        match totals.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(f64::from(totals[middle])),
+            _ => Some(f64::midpoint(
+                f64::from(totals[middle - 1]),
+                f64::from(totals[middle]),
+            )),
+        }
+    }
+
+    /// The mean total.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean(&self) -> Option<f64> {
+        if self.elves.is_empty() {
+            return None;
+        }
+        let sum: u64 = self.elves.iter().map(|elf| u64::from(elf.total)).sum();
+        Some(sum as f64 / self.elves.len() as f64)
+    }
+
+    /// Splits the Elves into `count` buckets of equal percentile ranges, from the lowest, so that
+    /// 4 gives the quartiles. The percentile of an Elf is the share of the Elves with a lower
+    /// total, so Elves with the same total are always in the same bucket, and a bucket may be
+    /// empty. The Elves of each bucket go from the fewest calories to the most.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is 0.
+    #[must_use]
+    pub fn percentile_buckets(&self, count: usize) -> Vec<Vec<&Elf>> {
+        assert!(count > 0, "there must be at least one bucket");
+        let mut elves: Vec<_> = self.elves.iter().collect();
+        elves.sort_by_key(|elf| (elf.total, elf.index));
+        let mut buckets = vec![Vec::new(); count];
+        for elf in &elves {
+            let lower = elves.partition_point(|other| other.total < elf.total);
+            buckets[lower * count / elves.len()].push(*elf);
+        }
+        buckets
+    }
+
+    /// The Elves that carry nothing at all, in the order of the input.
+    pub fn empty_packs(&self) -> impl Iterator<Item = &Elf> {
+        self.elves.iter().filter(|elf| elf.items.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_max_calories, find_top_3_max_calories, generator, Day01};
+    use common::Solution;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
+
+    fn indices<'a>(elves: impl IntoIterator<Item = &'a Elf>) -> Vec<usize> {
+        elves.into_iter().map(Elf::index).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let inventory = Inventory::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(inventory.elves().len(), 5);
+        assert_eq!(inventory.elves()[1].items(), [4000]);
+        assert_eq!(inventory.elves()[3].items(), [7000, 8000, 9000]);
+        assert_eq!(inventory.elves()[3].total(), 24_000);
+
+        let error = Inventory::parse("1000\n\n2x00").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+        let error = Inventory::parse("4000000000\n4000000000").unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn test_parse_normalized() {
+        let inventory = Inventory::parse(&SAMPLE_INPUT.replace('\n', "\r\n")).unwrap();
+        assert_eq!(inventory, Inventory::parse(SAMPLE_INPUT).unwrap());
+        let inventory = Inventory::parse("1000 \n2000\n\n3000\n\n").unwrap();
+        assert_eq!(inventory, Inventory::parse("1000\n2000\n\n3000").unwrap());
+        assert_eq!(indices(inventory.empty_packs()), []);
+        assert_eq!(inventory.mean(), Some(3000.0));
+
+        let error = Inventory::parse("1000\r\n\r\n2x00\r\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+        assert_eq!(error.line_text, "2x00");
+    }
+
+    #[test]
+    fn test_ranking() {
+        let inventory = Inventory::parse("5\n\n7\n\n3\n4\n\n7\n\n1").unwrap();
+        let ranking: Vec<_> = inventory
+            .ranking()
+            .iter()
+            .map(|ranked| (ranked.rank, ranked.elf.index(), ranked.elf.total()))
+            .collect();
+        assert_eq!(
+            ranking,
+            [(1, 1, 7), (1, 2, 7), (1, 3, 7), (4, 0, 5), (5, 4, 1)]
+        );
+
+        assert_eq!(
+            indices(inventory.top(1).iter().map(|ranked| ranked.elf)),
+            [1, 2, 3]
+        );
+        assert_eq!(inventory.top(4).len(), 4);
+        assert_eq!(inventory.top(0), []);
+        assert_eq!(
+            inventory
+                .ties()
+                .into_iter()
+                .map(indices)
+                .collect::<Vec<_>>(),
+            [[1, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn test_statistics() {
+        let inventory = Inventory::parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(inventory.median(), Some(10_000.0));
+        assert_eq!(inventory.mean(), Some(11_000.0));
+
+        let inventory = Inventory::parse("1\n\n2\n\n3\n\n10").unwrap();
+        assert_eq!(inventory.median(), Some(2.5));
+        assert_eq!(inventory.mean(), Some(4.0));
+    }
+
+    #[test]
+    fn test_percentile_buckets() {
+        let inventory = Inventory::parse(SAMPLE_INPUT).unwrap();
+        let quartiles: Vec<_> = inventory
+            .percentile_buckets(4)
+            .into_iter()
+            .map(indices)
+            .collect();
+        assert_eq!(quartiles, [vec![1, 0], vec![4], vec![2], vec![3]]);
+        assert_eq!(
+            indices(inventory.percentile_buckets(1).remove(0)),
+            [1, 0, 4, 2, 3]
+        );
+
+        let inventory = Inventory::parse("5\n\n5\n\n5\n\n9").unwrap();
+        let halves: Vec<_> = inventory
+            .percentile_buckets(2)
+            .into_iter()
+            .map(indices)
+            .collect();
+        assert_eq!(halves, [vec![0, 1, 2], vec![3]]);
+    }
+
+    #[test]
+    #[should_panic = "there must be at least one bucket"]
+    fn test_no_buckets() {
+        let _ = Inventory::parse(SAMPLE_INPUT)
+            .unwrap()
+            .percentile_buckets(0);
+    }
+
+    #[test]
+    fn test_empty_packs() {
+        let inventory = Inventory::parse("1000\n\n\n2000\n\n\n\n3000\n\n\n\n\n\n4000").unwrap();
+        assert_eq!(indices(inventory.empty_packs()), [2, 4, 5]);
+        assert_eq!(inventory.elves().len(), 7);
+        assert_eq!(inventory.top(1)[0].elf.total(), 4000);
+        assert_eq!(indices(Inventory::parse("1000").unwrap().empty_packs()), []);
+    }
+
+    #[test]
+    fn test_matches_solution() {
+        let crlf_input = SAMPLE_INPUT.replace('\n', "\r\n") + "\r\n";
+        for input in [
+            SAMPLE_INPUT,
+            &crlf_input,
+            "1000\n\n\n2000\n",
+            "1000\n\n\n\n2000\n",
+            "\n\n1000\n\n\n\n\n2000\n3000",
+            &generator::generate(10_000, 1),
+        ] {
+            let inventory = Inventory::parse(input).unwrap();
+            let calories = Day01::parse(&normalize(input)).unwrap();
+            let totals: Vec<_> = inventory.elves().iter().map(Elf::total).collect();
+            assert_eq!(totals, calories, "{input:?}");
+            let top_3: u32 = inventory
+                .ranking()
+                .iter()
+                .take(3)
+                .map(|ranked| ranked.elf.total())
+                .sum();
+            assert_eq!(
+                inventory.ranking()[0].elf.total(),
+                find_max_calories(&calories)
+            );
+            assert_eq!(top_3, find_top_3_max_calories(&calories));
+        }
+    }
+}
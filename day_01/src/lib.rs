@@ -1,68 +1,347 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
-const INPUT: &str = include_str!("input.txt");
+#[cfg(feature = "embedded-input")]
+pub mod embedded;
+pub mod generator;
+pub mod inventory;
+pub mod rebalance;
+pub mod swar;
+
+use common::{
+    input::normalize,
+    stream::{LineReader, ReadError},
+    ParseError, Solution,
+};
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
 
 #[must_use]
-pub fn part_1() -> u32 {
-    find_max_calories(INPUT)
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
-pub fn part_2() -> u32 {
-    find_top_3_max_calories(INPUT)
+pub fn part_2(input: &str) -> u32 {
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not a number of calories, or if a total does not fit in 32 bits.
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Day01::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not a number of calories, or if a total does not fit in 32 bits.
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Day01::try_part_2(input)
+}
+
+/// Solves part 1 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<u32, ReadError> {
+    let mut max_calories = 0;
+    read_calories_per_elf(reader, |calories| {
+        max_calories = max_calories.max(calories);
+    })?;
+    Ok(max_calories)
 }
 
-fn find_max_calories(input: &str) -> u32 {
-    calories_per_elf(input).max().unwrap()
+/// Solves part 2 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<u32, ReadError> {
+    let mut top_3 = [0; 3];
+    read_calories_per_elf(reader, |calories| {
+        if calories > top_3[0] {
+            top_3[0] = calories;
+            top_3.sort_unstable();
+        }
+    })?;
+    Ok(top_3.iter().sum())
 }
 
-fn find_top_3_max_calories(input: &str) -> u32 {
-    let mut counts: Vec<_> = calories_per_elf(input).collect();
+/// The `n` largest totals of calories carried by an Elf, from the largest, or all of them if
+/// there are fewer Elves. The totals are parsed like [`Day01::parse`] does, then go through a
+/// heap of the `n` largest so far, so selecting them takes O(e log n) time for e Elves rather
+/// than the O(e log e) of sorting them all. The input is normalized first, like for the parts of
+/// the puzzle.
+///
+/// # Errors
+///
+/// Returns an error pointing at the first part of the input that does not match the puzzle.
+pub fn top_n_calories(input: &str, n: usize) -> Result<Vec<u32>, ParseError> {
+    let totals = swar::calories_per_elf(&normalize(input))?;
+    // `n` can be far more than the number of Elves, to ask for all of them.
+    let mut heap = BinaryHeap::new();
+    for calories in totals {
+        heap.push(Reverse(calories));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(calories)| calories)
+        .collect())
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed<'a> = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        swar::calories_per_elf(input)
+    }
+
+    fn solve_1(_input: &str, calories: &Vec<u32>) -> Result<u32, ParseError> {
+        Ok(find_max_calories(calories))
+    }
+
+    fn solve_2(_input: &str, calories: &Vec<u32>) -> Result<u32, ParseError> {
+        Ok(find_top_3_max_calories(calories))
+    }
+}
+
+fn find_max_calories(calories: &[u32]) -> u32 {
+    calories.iter().copied().max().unwrap_or(0)
+}
+
+fn find_top_3_max_calories(calories: &[u32]) -> u32 {
+    let mut counts = calories.to_vec();
     counts.sort_unstable();
     counts.into_iter().rev().take(3).sum()
 }
 
-fn calories_per_elf(input: &str) -> impl Iterator<Item = u32> + '_ {
-    input
-        .split("\n\n")
-        .map(|i| i.lines().map(parse_calories).sum())
+/// The total calories of each Elf, parsed a line at a time. This is the reference for the faster
+/// [`swar::calories_per_elf`], which the solution uses.
+///
+/// # Errors
+///
+/// Returns an error pointing at the first part of the input that does not match the puzzle.
+pub fn calories_per_elf_scalar(input: &str) -> Result<Vec<u32>, ParseError> {
+    calories_per_elf(input).collect()
+}
+
+fn calories_per_elf(input: &str) -> impl Iterator<Item = Result<u32, ParseError>> + '_ {
+    input.split("\n\n").map(|i| {
+        i.lines().try_fold(0_u32, |total, line| {
+            total
+                .checked_add(parse_calories(input, line)?)
+                .ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        line,
+                        "a total number of calories that fits in 32 bits",
+                    )
+                })
+        })
+    })
+}
+
+/// Streams the total calories of each elf to `f`, in the order of the input.
+fn read_calories_per_elf(reader: impl BufRead, mut f: impl FnMut(u32)) -> Result<(), ReadError> {
+    let mut reader = LineReader::new(reader);
+    let mut total = 0_u32;
+    while let Some(is_blank) = reader.parse_line(|line| {
+        if !line.is_empty() {
+            total = total
+                .checked_add(parse_calories(line, line)?)
+                .ok_or_else(|| {
+                    ParseError::at(
+                        line,
+                        line,
+                        "a total number of calories that fits in 32 bits",
+                    )
+                })?;
+        }
+        Ok(line.is_empty())
+    }) {
+        if is_blank? {
+            f(total);
+            total = 0;
+        }
+    }
+    f(total);
+    Ok(())
 }
 
 // Custom parse function for minor speed improvement
-fn parse_calories(line: &str) -> u32 {
-    let mut magnitude = 1;
-    let mut result = 0;
-    for digit in line.bytes().rev() {
-        result += u32::from(digit - b'0') * magnitude;
-        magnitude *= 10;
-    }
-    result
+fn parse_calories(input: &str, line: &str) -> Result<u32, ParseError> {
+    let mut result: u32 = 0;
+    for (i, digit) in line.bytes().enumerate() {
+        if !digit.is_ascii_digit() {
+            return Err(ParseError::at_char(input, &line[i..], "a digit"));
+        }
+        result = result
+            .checked_mul(10)
+            .and_then(|result| result.checked_add(u32::from(digit - b'0')))
+            .ok_or_else(|| {
+                ParseError::at(input, line, "a number of calories that fits in 32 bits")
+            })?;
+    }
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, Answer};
+    use proptest::prelude::*;
 
-    const SAMPLE_INPUT: &str = include_str!("sample-input.txt");
+    const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
 
     #[test]
     fn test_part_1_sample() {
-        assert_eq!(find_max_calories(SAMPLE_INPUT), 24_000);
+        assert_eq!(Day01::try_part_1(SAMPLE_INPUT), Ok(24_000));
     }
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(), 70698);
+        let Some(input) = real_input(1) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(1, 1, "input"));
     }
 
     #[test]
     fn test_part_2_sample() {
-        assert_eq!(find_top_3_max_calories(SAMPLE_INPUT), 45000);
+        assert_eq!(Day01::try_part_2(SAMPLE_INPUT), Ok(45000));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(), 206_643);
+        let Some(input) = real_input(1) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(1, 2, "input"));
+    }
+
+    #[test]
+    fn test_top_n_calories() {
+        assert_eq!(
+            top_n_calories(SAMPLE_INPUT, 3),
+            Ok(vec![24_000, 11_000, 10_000])
+        );
+        assert_eq!(top_n_calories(SAMPLE_INPUT, 0), Ok(vec![]));
+        assert_eq!(
+            top_n_calories(SAMPLE_INPUT, 10),
+            Ok(vec![24_000, 11_000, 10_000, 6_000, 4_000])
+        );
+        assert_eq!(top_n_calories("5\n\n7\n\n7", 2), Ok(vec![7, 7]));
+
+        let input = generator::generate(10_000, 1);
+        let top_3 = top_n_calories(&input, 3).unwrap();
+        assert_eq!(top_3.iter().sum::<u32>(), Day01::part_2(&input));
+        assert_eq!(top_3[0], Day01::part_1(&input));
+
+        let all = top_n_calories(SAMPLE_INPUT, 10).unwrap();
+        assert_eq!(top_n_calories(SAMPLE_INPUT, usize::MAX), Ok(all.clone()));
+        assert_eq!(top_n_calories(SAMPLE_INPUT, 1 << 40), Ok(all));
+
+        let error = top_n_calories("1000\n\n20x0", 1).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn test_top_n_calories_normalized() {
+        let calories = Day01::parse(SAMPLE_INPUT).unwrap();
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            let top_3 = top_n_calories(&input, 3).unwrap();
+            assert_eq!(
+                top_3.iter().sum::<u32>(),
+                find_top_3_max_calories(&calories)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Day01::try_part_1("1000\n20x0\n\n3000").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "`x`");
+
+        let error = Day01::try_part_2("1000\n99999999999").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = Day01::try_part_1("4000000000\n4000000000").unwrap_err();
+        assert_eq!(
+            error.expected,
+            "a total number of calories that fits in 32 bits"
+        );
+    }
+
+    #[test]
+    fn test_from_reader() {
+        for input in [SAMPLE_INPUT, &generator::generate(10_000, 1)] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), Day01::try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), Day01::try_part_2(input));
+        }
+
+        let Err(ReadError::Parse(error)) = part_2_from_reader(
+            "1000
+
+20x0
+"
+            .as_bytes(),
+        ) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn test_whitespace() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(Day01::part_1(&input), Day01::part_1(SAMPLE_INPUT));
+            assert_eq!(Day01::part_2(&input), Day01::part_2(SAMPLE_INPUT));
+            assert_eq!(
+                part_1_from_reader(input.as_bytes()).unwrap(),
+                Day01::part_1(SAMPLE_INPUT)
+            );
+            assert_eq!(
+                part_2_from_reader(input.as_bytes()).unwrap(),
+                Day01::part_2(SAMPLE_INPUT)
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_mutated_input(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day01>(&input);
+        }
+
+        #[test]
+        fn test_random_input(input in ".*") {
+            solve_both::<Day01>(&input);
+        }
+
+        #[test]
+        fn test_reference(elves in prop::collection::vec(
+            prop::collection::vec(0..100_000_u32, 1..10),
+            1..20,
+        )) {
+            let input = elves
+                .iter()
+                .map(|calories| calories.iter().map(u32::to_string).collect::<Vec<_>>().join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let mut totals: Vec<u32> = elves.iter().map(|calories| calories.iter().sum()).collect();
+            totals.sort_unstable_by(|a, b| b.cmp(a));
+            prop_assert_eq!(Day01::try_part_1(&input), Ok(totals[0]));
+            prop_assert_eq!(Day01::try_part_2(&input), Ok(totals.iter().take(3).sum()));
+        }
     }
 }
@@ -0,0 +1,363 @@
+//! Plans how to move items between the Elves so that the heaviest pack is as light as possible.
+//!
+//! This is multiway number partitioning, which is NP-hard, so [`rebalance`] only searches for the
+//! best plan with [`exact`] when there are few items, and settles for the one found by [`greedy`]
+//! otherwise.
+
+use crate::inventory::{Elf, Inventory};
+use std::cmp::Reverse;
+
+/// The most items for which [`exact`] searches for the best plan.
+pub const EXACT_MAX_ITEMS: usize = 12;
+
+/// Moving one item from the pack of an Elf to another, with the Elves and the item given by their
+/// indices in the [`Inventory`] that was planned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub from: usize,
+    pub item: usize,
+    pub calories: u32,
+    pub to: usize,
+}
+
+/// The moves to make, each item moving at most once, and the total calories of each Elf after
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    pub totals: Vec<u32>,
+}
+
+impl Plan {
+    /// The total of the heaviest pack after the moves.
+    #[must_use]
+    pub fn max_total(&self) -> u32 {
+        self.totals.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// The best plan that [`exact`] can find if there are at most [`EXACT_MAX_ITEMS`] items, and the
+/// one from [`greedy`] otherwise. No more than `max_moves` items are moved, if it is given.
+#[must_use]
+pub fn rebalance(inventory: &Inventory, max_moves: Option<usize>) -> Plan {
+    exact(inventory, max_moves).unwrap_or_else(|| greedy(inventory, max_moves))
+}
+
+/// An item, where it comes from and where the plan puts it.
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    owner: usize,
+    index: usize,
+    calories: u32,
+}
+
+fn items(inventory: &Inventory) -> Vec<Item> {
+    inventory
+        .elves()
+        .iter()
+        .flat_map(|elf| {
+            elf.items()
+                .iter()
+                .enumerate()
+                .map(|(index, &calories)| Item {
+                    owner: elf.index(),
+                    index,
+                    calories,
+                })
+        })
+        .collect()
+}
+
+/// The plan that puts each item with the Elf at the same position in `assignment`.
+fn plan(inventory: &Inventory, items: &[Item], assignment: &[usize]) -> Plan {
+    let mut totals = vec![0; inventory.elves().len()];
+    let mut moves = Vec::new();
+    for (item, &to) in items.iter().zip(assignment) {
+        totals[to] += item.calories;
+        if to != item.owner {
+            moves.push(Move {
+                from: item.owner,
+                item: item.index,
+                calories: item.calories,
+                to,
+            });
+        }
+    }
+    Plan { moves, totals }
+}
+
+/// Repeatedly moves an item from the heaviest pack to the lightest one, picking the item that
+/// leaves the two closest to each other, for as long as that makes the heavier of the two
+/// lighter than the heaviest pack was. An item that was moved already can be moved again without
+/// counting against `max_moves`.
+///
+/// Every move brings the totals closer together, so this always ends, but it can miss plans that
+/// would need an item to go somewhere else than the lightest pack first.
+#[must_use]
+pub fn greedy(inventory: &Inventory, max_moves: Option<usize>) -> Plan {
+    let items = items(inventory);
+    let assignment = greedy_assignment(inventory, &items, max_moves.unwrap_or(usize::MAX));
+    plan(inventory, &items, &assignment)
+}
+
+fn greedy_assignment(inventory: &Inventory, items: &[Item], max_moves: usize) -> Vec<usize> {
+    let mut assignment: Vec<_> = items.iter().map(|item| item.owner).collect();
+    let mut totals: Vec<_> = inventory.elves().iter().map(Elf::total).collect();
+    let mut moves = 0;
+    loop {
+        let heaviest = (0..totals.len()).max_by_key(|&elf| (totals[elf], usize::MAX - elf));
+        let lightest = (0..totals.len()).min_by_key(|&elf| (totals[elf], elf));
+        let (Some(heaviest), Some(lightest)) = (heaviest, lightest) else {
+            break;
+        };
+        let (high, low) = (totals[heaviest], totals[lightest]);
+        let best = (0..items.len())
+            .filter(|&i| {
+                assignment[i] == heaviest
+                    && items[i].calories > 0
+                    && u64::from(low) + u64::from(items[i].calories) < u64::from(high)
+            })
+            .filter(|&i| moves < max_moves || assignment[i] != items[i].owner)
+            .min_by_key(|&i| (high - items[i].calories).max(low + items[i].calories));
+        let Some(i) = best else { break };
+
+        moves -= usize::from(assignment[i] != items[i].owner);
+        moves += usize::from(lightest != items[i].owner);
+        assignment[i] = lightest;
+        totals[heaviest] -= items[i].calories;
+        totals[lightest] += items[i].calories;
+    }
+    assignment
+}
+
+/// The plan with the lightest heaviest pack, and the fewest moves among those, found by trying
+/// every assignment of the items to the Elves that could still beat the best one so far, starting
+/// from the plan of [`greedy`]. This is `None` if there are more than [`EXACT_MAX_ITEMS`] items.
+#[must_use]
+pub fn exact(inventory: &Inventory, max_moves: Option<usize>) -> Option<Plan> {
+    let items = items(inventory);
+    if items.len() > EXACT_MAX_ITEMS {
+        return None;
+    }
+    let num_elves = u64::try_from(inventory.elves().len()).unwrap();
+    let sum: u64 = items.iter().map(|item| u64::from(item.calories)).sum();
+    let largest = items.iter().map(|item| u64::from(item.calories)).max();
+
+    let max_moves = max_moves.unwrap_or(usize::MAX);
+    let greedy = greedy_assignment(inventory, &items, max_moves);
+    let greedy_plan = plan(inventory, &items, &greedy);
+
+    // Placing the largest items first finds good plans early, which prunes the most.
+    let mut order: Vec<_> = (0..items.len()).collect();
+    order.sort_by_key(|&i| (Reverse(items[i].calories), i));
+    let mut remaining = vec![0; inventory.elves().len()];
+    for item in &items {
+        remaining[item.owner] += 1;
+    }
+    let mut search = Search {
+        items: &items,
+        order: &order,
+        lower_bound: sum.div_ceil(num_elves).max(largest.unwrap_or(0)),
+        max_moves,
+        totals: vec![0; inventory.elves().len()],
+        remaining,
+        assignment: items.iter().map(|item| item.owner).collect(),
+        best: (
+            u64::from(greedy_plan.max_total()),
+            greedy_plan.moves.len(),
+            greedy,
+        ),
+    };
+    search.place(0, 0);
+    Some(plan(inventory, &items, &search.best.2))
+}
+
+struct Search<'a> {
+    items: &'a [Item],
+    order: &'a [usize],
+    /// No plan can have a lighter heaviest pack than this.
+    lower_bound: u64,
+    max_moves: usize,
+    /// The totals of the items placed so far, which can add up to more than 32 bits in plans
+    /// that are worse than leaving the items where they are.
+    totals: Vec<u64>,
+    /// The number of items of each Elf that are still to be placed.
+    remaining: Vec<usize>,
+    assignment: Vec<usize>,
+    /// The heaviest pack, the number of moves and the assignment of the best plan so far.
+    best: (u64, usize, Vec<usize>),
+}
+
+impl Search<'_> {
+    /// Tries every Elf for the `next` item in the order, the one that has it first.
+    fn place(&mut self, next: usize, moves: usize) {
+        let heaviest = self.totals.iter().copied().max().unwrap_or(0);
+        let (best_max, best_moves, _) = &self.best;
+        if (heaviest.max(self.lower_bound), moves) >= (*best_max, *best_moves) {
+            return;
+        }
+        let Some(&i) = self.order.get(next) else {
+            self.best = (heaviest, moves, self.assignment.clone());
+            return;
+        };
+
+        let owner = self.items[i].owner;
+        self.remaining[owner] -= 1;
+        // Elves that have none of the items left and the same total are interchangeable, so only
+        // the first of them needs to be tried.
+        let mut tried_totals = Vec::new();
+        for elf in [owner]
+            .into_iter()
+            .chain((0..self.totals.len()).filter(|&elf| elf != owner))
+        {
+            let moves = moves + usize::from(elf != owner);
+            if moves > self.max_moves {
+                break;
+            }
+            if elf != owner && self.remaining[elf] == 0 {
+                if tried_totals.contains(&self.totals[elf]) {
+                    continue;
+                }
+                tried_totals.push(self.totals[elf]);
+            }
+            self.totals[elf] += u64::from(self.items[i].calories);
+            self.assignment[i] = elf;
+            self.place(next + 1, moves);
+            self.totals[elf] -= u64::from(self.items[i].calories);
+        }
+        self.assignment[i] = owner;
+        self.remaining[owner] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator;
+    use proptest::prelude::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
+
+    /// Makes the moves of the plan and checks that they lead to its totals.
+    fn check(inventory: &Inventory, plan: &Plan) {
+        let mut packs: Vec<Vec<Option<u32>>> = inventory
+            .elves()
+            .iter()
+            .map(|elf| elf.items().iter().copied().map(Some).collect())
+            .collect();
+        let mut totals: Vec<_> = inventory.elves().iter().map(Elf::total).collect();
+        for step in &plan.moves {
+            assert_eq!(packs[step.from][step.item].take(), Some(step.calories));
+            assert_ne!(step.from, step.to);
+            totals[step.from] -= step.calories;
+            totals[step.to] += step.calories;
+        }
+        assert_eq!(totals, plan.totals);
+    }
+
+    #[test]
+    fn test_exact() {
+        let inventory = Inventory::parse(SAMPLE_INPUT).unwrap();
+        let plan = exact(&inventory, None).unwrap();
+        check(&inventory, &plan);
+        assert_eq!(plan.totals, [11_000; 5]);
+
+        let plan = exact(&inventory, Some(0)).unwrap();
+        assert_eq!((plan.moves.len(), plan.max_total()), (0, 24_000));
+        let plan = exact(&inventory, Some(1)).unwrap();
+        check(&inventory, &plan);
+        assert_eq!(plan.moves.len(), 1);
+        assert_eq!((plan.moves[0].from, plan.moves[0].item), (3, 2));
+        assert_eq!(plan.max_total(), 15_000);
+
+        let inventory = Inventory::parse(&"1\n".repeat(EXACT_MAX_ITEMS + 1)).unwrap();
+        assert_eq!(exact(&inventory, None), None);
+    }
+
+    #[test]
+    fn test_exact_fewest_moves() {
+        // Moving the 4 alone is as good as moving both 2s.
+        let inventory = Inventory::parse("\n\n2\n2\n4").unwrap();
+        let plan = exact(&inventory, None).unwrap();
+        check(&inventory, &plan);
+        assert_eq!(plan.totals, [4, 4]);
+        assert_eq!(
+            plan.moves,
+            [Move {
+                from: 1,
+                item: 2,
+                calories: 4,
+                to: 0
+            }]
+        );
+
+        let inventory = Inventory::parse("4000000000\n\n4000000000\n\n4000000000").unwrap();
+        assert_eq!(exact(&inventory, None).unwrap().moves, []);
+    }
+
+    #[test]
+    fn test_greedy() {
+        let inventory = Inventory::parse(SAMPLE_INPUT).unwrap();
+        let plan = greedy(&inventory, Some(1));
+        check(&inventory, &plan);
+        assert_eq!((plan.moves.len(), plan.max_total()), (1, 15_000));
+        let plan = greedy(&inventory, None);
+        check(&inventory, &plan);
+        assert!(plan.max_total() < 24_000);
+
+        let inventory = Inventory::parse(&generator::generate(10_000, 1)).unwrap();
+        let plan = greedy(&inventory, Some(50));
+        check(&inventory, &plan);
+        assert!(plan.moves.len() <= 50);
+        let heaviest = inventory.ranking()[0].elf.total();
+        assert!(plan.max_total() < heaviest);
+        assert_eq!(rebalance(&inventory, Some(50)), plan);
+    }
+
+    #[test]
+    fn test_zero_calories() {
+        // Moving a 0 would not make the heaviest pack any lighter.
+        let inventory = Inventory::parse("0\n0\n10\n\n1").unwrap();
+        let plan = greedy(&inventory, None);
+        check(&inventory, &plan);
+        assert_eq!(plan.moves, []);
+        assert_eq!(exact(&inventory, None).unwrap().moves, []);
+
+        let inventory = Inventory::parse("0\n6\n0\n4\n\n1").unwrap();
+        let plan = greedy(&inventory, Some(1));
+        check(&inventory, &plan);
+        assert_eq!((plan.moves.len(), plan.max_total()), (1, 6));
+    }
+
+    #[test]
+    fn test_empty_packs() {
+        let inventory = Inventory::parse("\n\n9\n7").unwrap();
+        let plan = rebalance(&inventory, None);
+        check(&inventory, &plan);
+        assert_eq!((plan.moves.len(), plan.max_total()), (1, 9));
+    }
+
+    proptest! {
+        #[test]
+        fn test_exact_beats_greedy(
+            elves in prop::collection::vec(prop::collection::vec(1..100_u32, 0..4), 1..4),
+            max_moves in prop::option::of(0..4_usize),
+        ) {
+            let input = elves
+                .iter()
+                .map(|items| items.iter().map(u32::to_string).collect::<Vec<_>>().join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let inventory = Inventory::parse(&input).unwrap();
+            let greedy = greedy(&inventory, max_moves);
+            let exact = exact(&inventory, max_moves).unwrap();
+            check(&inventory, &greedy);
+            check(&inventory, &exact);
+            prop_assert!(exact.max_total() <= greedy.max_total());
+            if let Some(max_moves) = max_moves {
+                prop_assert!(greedy.moves.len() <= max_moves);
+                prop_assert!(exact.moves.len() <= max_moves);
+            }
+        }
+    }
+}
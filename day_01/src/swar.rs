@@ -0,0 +1,174 @@
+//! A parser that reads the input a word of 8 bytes at a time (SWAR, for SIMD within a register)
+//! rather than a byte at a time, for the usual shape of the input.
+//!
+//! For each line, one word is enough to find how many digits it starts with and to add them up
+//! into a number with a few multiplications. Anything out of the ordinary, like a number of more
+//! than 7 digits, an invalid character or several blank lines in a row, makes the parser hand the
+//! whole input over to the scalar one, which is the reference and reports the errors.
+
+use crate::calories_per_elf_scalar;
+use common::ParseError;
+
+/// A 1 in every byte of a word.
+const ONES: u64 = 0x0101_0101_0101_0101;
+/// The highest bit of every byte of a word.
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// The total calories of each Elf, in the order of the input.
+///
+/// # Errors
+///
+/// Returns an error pointing at the first part of the input that does not match the puzzle.
+pub fn calories_per_elf(input: &str) -> Result<Vec<u32>, ParseError> {
+    match parse_fast(input.as_bytes()) {
+        Some(totals) => Ok(totals),
+        None => calories_per_elf_scalar(input),
+    }
+}
+
+/// Parses the input if it only has numbers of up to 7 digits on lines of their own, with single
+/// blank lines between the Elves, and totals that fit in 32 bits.
+fn parse_fast(bytes: &[u8]) -> Option<Vec<u32>> {
+    let mut totals = Vec::new();
+    let mut total = 0_u32;
+    let mut after_blank_line = false;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let word = load(bytes, pos);
+        let len = leading_digits(word);
+        if len == 0 {
+            // Only a blank line right after a number separates two Elves.
+            if bytes[pos] != b'\n' || pos == 0 || after_blank_line {
+                return None;
+            }
+            totals.push(total);
+            total = 0;
+            after_blank_line = true;
+            pos += 1;
+            continue;
+        }
+
+        let end = pos + len;
+        if len == 8 || bytes.get(end).is_some_and(|&byte| byte != b'\n') {
+            return None;
+        }
+        total = total.checked_add(parse_digits(word, len))?;
+        after_blank_line = false;
+        pos = end + 1;
+    }
+    totals.push(total);
+    Some(totals)
+}
+
+/// The 8 bytes from `pos`, padded with zeros past the end, with the first one lowest.
+fn load(bytes: &[u8], pos: usize) -> u64 {
+    if let Some(word) = bytes.get(pos..pos + 8) {
+        u64::from_le_bytes(word.try_into().unwrap())
+    } else {
+        let mut word = [0; 8];
+        word[..bytes.len() - pos].copy_from_slice(&bytes[pos..]);
+        u64::from_le_bytes(word)
+    }
+}
+
+/// The number of ASCII digits that the word starts with, up to 8.
+fn leading_digits(word: u64) -> usize {
+    // A byte is a digit if its upper half is 3 and its lower half is at most 9, which is when
+    // adding 6 to it does not carry. Neither step carries into the next byte.
+    let upper = (word & 0xF0F0_F0F0_F0F0_F0F0) ^ (0x30 * ONES);
+    let lower = ((word & 0x0F0F_0F0F_0F0F_0F0F) + 0x06 * ONES) & 0xF0F0_F0F0_F0F0_F0F0;
+    let not_digit = upper | lower;
+    // The highest bit of each byte that is not 0, that is of each byte that is not a digit.
+    let not_digit = (((not_digit & !HIGH_BITS) + !HIGH_BITS) | not_digit) & HIGH_BITS;
+    not_digit.trailing_zeros() as usize / 8
+}
+
+/// The number written by the first `len` bytes of the word, which must be 1 to 8 digits.
+fn parse_digits(word: u64, len: usize) -> u32 {
+    // Moving the digits to the top makes the bytes below them leading zeros. Whatever came
+    // after the digits is shifted out, along with any borrow from subtracting '0' from it.
+    let mut value = word.wrapping_sub(u64::from(b'0') * ONES) << (8 * (8 - len));
+    // Combine pairs of digits, then pairs of pairs, and so on, into ever wider lanes.
+    value = (value * 10 + (value >> 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value * 100 + (value >> 16)) & 0x0000_FFFF_0000_FFFF;
+    value = (value * 10_000 + (value >> 32)) & 0x0000_0000_FFFF_FFFF;
+    u32::try_from(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator;
+    use proptest::prelude::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
+
+    fn word(bytes: &[u8]) -> u64 {
+        load(bytes, 0)
+    }
+
+    #[test]
+    fn test_leading_digits() {
+        assert_eq!(leading_digits(word(b"12345\n67")), 5);
+        assert_eq!(leading_digits(word(b"\n1234567")), 0);
+        assert_eq!(leading_digits(word(b"12345678")), 8);
+        assert_eq!(leading_digits(word(b"12")), 2);
+        assert_eq!(leading_digits(word(b"1/2:3")), 1);
+        assert_eq!(leading_digits(word(b"9\xFA\xB9")), 1);
+        assert_eq!(leading_digits(word(b"0\x80")), 1);
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        assert_eq!(parse_digits(word(b"12345\n67"), 5), 12_345);
+        assert_eq!(parse_digits(word(b"7\n\n"), 1), 7);
+        assert_eq!(parse_digits(word(b"00420"), 5), 420);
+        assert_eq!(parse_digits(word(b"99999999"), 8), 99_999_999);
+    }
+
+    #[test]
+    fn test_calories_per_elf() {
+        assert_eq!(
+            parse_fast(SAMPLE_INPUT.as_bytes()),
+            Some(vec![6_000, 4_000, 11_000, 24_000, 10_000])
+        );
+        for input in ["", "1", "1\n", "1\n\n", "1\n\n2\n3", "1234567\n1"] {
+            assert_eq!(
+                parse_fast(input.as_bytes()).as_ref(),
+                calories_per_elf_scalar(input).as_ref().ok(),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fallback() {
+        let overflow = "9999999\n".repeat(430);
+        for input in [
+            "\n1",
+            "1\n\n\n2",
+            "12345678",
+            "1\r\n2",
+            "1 \n2",
+            "1000\n2x00",
+            &overflow,
+        ] {
+            assert_eq!(parse_fast(input.as_bytes()), None, "{input:?}");
+            assert_eq!(calories_per_elf(input), calories_per_elf_scalar(input));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_reference(seed in any::<u64>(), size in 0..2_000_usize) {
+            let input = generator::generate(size, seed);
+            prop_assert!(parse_fast(input.as_bytes()).is_some());
+            prop_assert_eq!(calories_per_elf(&input), calories_per_elf_scalar(&input));
+        }
+
+        #[test]
+        fn test_random_input(input in "[0-9\n]{0,40}|.*") {
+            prop_assert_eq!(calories_per_elf(&input), calories_per_elf_scalar(&input));
+        }
+    }
+}
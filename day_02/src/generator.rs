@@ -0,0 +1,31 @@
+use common::rng::Rng;
+
+/// Generates about `size` bytes of strategy guide, one random round per line.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 4);
+    while input.len() < size {
+        input.push(*rng.choose(&['A', 'B', 'C']));
+        input.push(' ');
+        input.push(*rng.choose(&['X', 'Y', 'Z']));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert_eq!(input.len(), 10_000);
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day02::try_part_1(&input).is_ok());
+        assert!(Day02::try_part_2(&input).is_ok());
+    }
+}
@@ -1,35 +1,132 @@
 #![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)]
 
+pub mod generator;
+
+use common::{
+    stream::{LineReader, ReadError},
+    ParseError, Solution,
+};
+use std::io::BufRead;
 use HandShape::{Paper, Rock, Scissors};
 use Outcome::{Draw, Loss, Win};
 
 #[must_use]
 pub fn part_1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let opponents_choice = HandShape::from(line.as_bytes()[0]);
-            let my_choice = HandShape::from(line.as_bytes()[2]);
-            my_choice.score() + my_choice.play_round(opponents_choice).score()
-        })
-        .sum()
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let opponents_choice = HandShape::from(line.as_bytes()[0]);
-            let outcome = Outcome::from(line.as_bytes()[2]);
-            let my_choice = match outcome {
-                Loss => opponents_choice.beats(),
-                Draw => opponents_choice,
-                Win => opponents_choice.beats().beats(),
-            };
-            my_choice.score() + outcome.score()
-        })
-        .sum()
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not an opponent's choice and a response separated by a space.
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Day02::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not an opponent's choice and a response separated by a space.
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Day02::try_part_2(input)
+}
+
+/// Solves part 1 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<u32, ReadError> {
+    total_score_from_reader(reader, score_round_1)
+}
+
+/// Solves part 2 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<u32, ReadError> {
+    total_score_from_reader(reader, score_round_2)
+}
+
+fn total_score_from_reader(
+    reader: impl BufRead,
+    score_round: fn((HandShape, u8)) -> u32,
+) -> Result<u32, ReadError> {
+    LineReader::new(reader)
+        .parse_lines(|line| parse_round(line, line))
+        .try_fold(0, |sum, round| Ok(sum + score_round(round?)))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed<'a> = StrategyGuide;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<StrategyGuide, ParseError> {
+        let rounds = input
+            .lines()
+            .map(|line| parse_round(input, line))
+            .collect::<Result<_, _>>()?;
+        Ok(StrategyGuide { rounds })
+    }
+
+    fn solve_1(_input: &str, guide: &StrategyGuide) -> Result<u32, ParseError> {
+        Ok(guide.rounds.iter().copied().map(score_round_1).sum())
+    }
+
+    fn solve_2(_input: &str, guide: &StrategyGuide) -> Result<u32, ParseError> {
+        Ok(guide.rounds.iter().copied().map(score_round_2).sum())
+    }
+}
+
+/// The score of a round when the response is the shape to play.
+fn score_round_1((opponents_choice, response): (HandShape, u8)) -> u32 {
+    let my_choice = HandShape::try_from(response).unwrap();
+    my_choice.score() + my_choice.play_round(opponents_choice).score()
+}
+
+/// The score of a round when the response is how the round needs to end.
+fn score_round_2((opponents_choice, response): (HandShape, u8)) -> u32 {
+    let outcome = Outcome::try_from(response).unwrap();
+    let my_choice = match outcome {
+        Loss => opponents_choice.beats(),
+        Draw => opponents_choice,
+        Win => opponents_choice.beats().beats(),
+    };
+    my_choice.score() + outcome.score()
+}
+
+/// The rounds of the encrypted strategy guide: the opponent's choice and the response, which is
+/// still a letter from `X` to `Z` because the two parts decrypt it differently.
+pub struct StrategyGuide {
+    rounds: Vec<(HandShape, u8)>,
+}
+
+/// Parses a line like `A Y` into the opponent's choice and the (still encoded) response.
+fn parse_round(input: &str, line: &str) -> Result<(HandShape, u8), ParseError> {
+    let bytes = line.as_bytes();
+    let opponents_choice = match bytes.first() {
+        Some(&byte @ b'A'..=b'C') => HandShape::try_from(byte).unwrap(),
+        _ => return Err(ParseError::at_char(input, line, "`A`, `B` or `C`")),
+    };
+    if bytes.get(1) != Some(&b' ') {
+        return Err(ParseError::at_char(input, &line[1..], "a space"));
+    }
+    let Some(&response @ b'X'..=b'Z') = bytes.get(2) else {
+        return Err(ParseError::at_char(input, &line[2..], "`X`, `Y` or `Z`"));
+    };
+    if bytes.len() > 3 {
+        return Err(ParseError::at_char(input, &line[3..], "end of line"));
+    }
+    Ok((opponents_choice, response))
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -39,13 +136,15 @@ enum HandShape {
     Scissors,
 }
 
-impl From<u8> for HandShape {
-    fn from(b: u8) -> Self {
+impl TryFrom<u8> for HandShape {
+    type Error = u8;
+
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
         match b {
-            b'A' | b'X' => Rock,
-            b'B' | b'Y' => Paper,
-            b'C' | b'Z' => Scissors,
-            _ => panic!(),
+            b'A' | b'X' => Ok(Rock),
+            b'B' | b'Y' => Ok(Paper),
+            b'C' | b'Z' => Ok(Scissors),
+            _ => Err(b),
         }
     }
 }
@@ -85,13 +184,15 @@ enum Outcome {
     Win,
 }
 
-impl From<u8> for Outcome {
-    fn from(b: u8) -> Self {
+impl TryFrom<u8> for Outcome {
+    type Error = u8;
+
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
         match b {
-            b'X' => Loss,
-            b'Y' => Draw,
-            b'Z' => Win,
-            _ => panic!(),
+            b'X' => Ok(Loss),
+            b'Y' => Ok(Draw),
+            b'Z' => Ok(Win),
+            _ => Err(b),
         }
     }
 }
@@ -109,9 +210,11 @@ impl Outcome {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -120,7 +223,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 13_809);
+        let Some(input) = real_input(2) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(2, 1, "input"));
     }
 
     #[test]
@@ -130,6 +234,71 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 12_316);
+        let Some(input) = real_input(2) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(2, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("A Y\nB X\nC Q\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.found, "`Q`");
+        assert_eq!(error.expected, "`X`, `Y` or `Z`");
+
+        let error = try_part_2("A Y\nD X\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = try_part_1("A\tY\n").unwrap_err();
+        assert_eq!(error.expected, "a space");
+
+        let error = try_part_2("A Y\nB\n").unwrap_err();
+        assert_eq!(error.found, "end of line");
+
+        let error = try_part_1("A YZ").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+    }
+
+    #[test]
+    fn from_reader_test() {
+        for input in [SAMPLE_INPUT, &generator::generate(10_000, 1)] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), try_part_2(input));
+        }
+
+        let Err(ReadError::Parse(error)) = part_1_from_reader("A Y\nB X\nC Q\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.found, "`Q`");
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+            assert_eq!(
+                part_1_from_reader(input.as_bytes()).unwrap(),
+                part_1(SAMPLE_INPUT)
+            );
+            assert_eq!(
+                part_2_from_reader(input.as_bytes()).unwrap(),
+                part_2(SAMPLE_INPUT)
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day02>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day02>(&input);
+        }
     }
 }
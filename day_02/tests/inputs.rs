@@ -0,0 +1,5 @@
+//! Checks the solution against every `inputs/<name>.txt` with answers in `answers/<name>.toml`.
+
+fn main() {
+    common::regression::run::<day_02::Day02>();
+}
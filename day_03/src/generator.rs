@@ -0,0 +1,54 @@
+use common::rng::Rng;
+
+/// Generates about `size` bytes of rucksacks, in groups of three. The compartments of each
+/// rucksack share exactly one item type, and the rucksacks of a group share exactly one badge.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut items: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+    let mut input = String::with_capacity(size + 200);
+    while input.len() < size {
+        rng.shuffle(&mut items);
+        let (badge, rest) = items.split_first().unwrap();
+        // Each rucksack of the group draws from its own 17 item types besides the badge, so
+        // that the badge is the only item type the three have in common. Its compartments draw
+        // from 8 of those each, and share either the badge or the one that is left.
+        for own_items in rest.chunks(17) {
+            let shared = *rng.choose(&[*badge, own_items[16]]);
+            let half_len = rng.range(2..=16);
+            let mut left = vec![shared];
+            if shared != *badge {
+                left.push(*badge);
+            }
+            while left.len() < half_len {
+                left.push(*rng.choose(&own_items[..8]));
+            }
+            let mut right = vec![shared];
+            while right.len() < half_len {
+                right.push(*rng.choose(&own_items[8..16]));
+            }
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            input.extend(left.into_iter().chain(right).map(char::from));
+            input.push('\n');
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((10_000..10_200).contains(&input.len()));
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day03::try_part_1(&input).is_ok());
+        assert!(Day03::try_part_2(&input).is_ok());
+    }
+}
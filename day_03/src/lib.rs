@@ -1,50 +1,183 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod generator;
+
+use common::{
+    stream::{LineReader, ReadError},
+    ParseError, Solution,
+};
+use std::io::BufRead;
+
 #[must_use]
 pub fn part_1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let (first_compartment, second_compartment) = line.as_bytes().split_at(line.len() / 2);
-            let common_item = *first_compartment
-                .iter()
-                .find(|item| second_compartment.contains(item))
-                .unwrap();
-            item_value(common_item)
-        })
-        .sum()
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u32 {
-    let rucksacks = input.lines().map(str::as_bytes).collect::<Vec<_>>();
-    rucksacks
-        .chunks(3)
-        .map(|group_rucksacks| {
-            let common_item = *group_rucksacks[0]
-                .iter()
-                .find(|item| group_rucksacks[1].contains(item) && group_rucksacks[2].contains(item))
-                .unwrap();
-            item_value(common_item)
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a rucksack holds something other than letters, holds an odd number of
+/// items, or has no item in common between its two compartments.
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Day03::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a rucksack holds something other than letters, if the rucksacks cannot be
+/// split into groups of three, or if a group has no item in common.
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Day03::try_part_2(input)
+}
+
+/// Solves part 1 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<u32, ReadError> {
+    LineReader::new(reader)
+        .parse_lines(|line| {
+            parse_rucksack(line, line)?;
+            common_item_value(line, line)
         })
         .sum()
 }
 
-fn item_value(item: u8) -> u32 {
-    u32::from(match item {
-        b'a'..=b'z' => item - b'a' + 1,
-        b'A'..=b'Z' => item - b'A' + 27,
-        _ => panic!(),
-    })
+/// Solves part 2 while reading the input, keeping one group of three lines of it in memory at a
+/// time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<u32, ReadError> {
+    let mut reader = LineReader::new(reader);
+    let mut group: Vec<String> = Vec::with_capacity(2);
+    let mut sum = 0;
+    while let Some(value) = reader.parse_line(|line| {
+        parse_rucksack(line, line)?;
+        if let [first, second] = &group[..] {
+            let value = badge_value(line, first, second, line)?;
+            group.clear();
+            Ok(value)
+        } else {
+            group.push(String::from(line));
+            Ok(0)
+        }
+    }) {
+        sum += value?;
+    }
+    if group.is_empty() {
+        Ok(sum)
+    } else {
+        Err(reader.end_of_input("a number of rucksacks that is a multiple of three"))
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed<'a> = Vec<&'a str>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        input
+            .lines()
+            .map(|line| parse_rucksack(input, line).map(|_| line))
+            .collect()
+    }
+
+    fn solve_1(input: &str, rucksacks: &Vec<&str>) -> Result<u32, ParseError> {
+        rucksacks
+            .iter()
+            .map(|rucksack| common_item_value(input, rucksack))
+            .sum()
+    }
+
+    fn solve_2(input: &str, rucksacks: &Vec<&str>) -> Result<u32, ParseError> {
+        rucksacks.chunks(3).try_fold(0, |sum, group_rucksacks| {
+            let [first, second, third] = group_rucksacks else {
+                return Err(ParseError::at(
+                    input,
+                    &input[input.len()..],
+                    "a number of rucksacks that is a multiple of three",
+                ));
+            };
+            Ok(sum + badge_value(input, first, second, third)?)
+        })
+    }
+}
+
+/// The value of the item in both compartments of the rucksack.
+fn common_item_value(input: &str, rucksack: &str) -> Result<u32, ParseError> {
+    let (first_compartment, second_compartment) = rucksack.as_bytes().split_at(rucksack.len() / 2);
+    let common_item = *first_compartment
+        .iter()
+        .find(|item| second_compartment.contains(item))
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                rucksack,
+                "an item in both compartments of the rucksack",
+            )
+        })?;
+    Ok(item_value(common_item).unwrap())
+}
+
+/// The value of the badge of a group: the item in all three rucksacks. Only `third` has to be a
+/// slice of `input`, as that is where a missing badge is reported.
+fn badge_value(input: &str, first: &str, second: &str, third: &str) -> Result<u32, ParseError> {
+    let badge = *first
+        .as_bytes()
+        .iter()
+        .find(|item| second.as_bytes().contains(item) && third.as_bytes().contains(item))
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                third,
+                "a rucksack sharing an item with the two before it",
+            )
+        })?;
+    Ok(item_value(badge).unwrap())
+}
+
+fn parse_rucksack<'a>(input: &str, line: &'a str) -> Result<&'a [u8], ParseError> {
+    if let Some(i) = line.find(|item: char| !item.is_ascii_alphabetic()) {
+        Err(ParseError::at_char(
+            input,
+            &line[i..],
+            "an item from `a` to `z` or `A` to `Z`",
+        ))
+    } else if line.len() % 2 == 1 {
+        Err(ParseError::at(input, line, "an even number of items"))
+    } else {
+        Ok(line.as_bytes())
+    }
+}
+
+fn item_value(item: u8) -> Option<u32> {
+    match item {
+        b'a'..=b'z' => Some(u32::from(item - b'a' + 1)),
+        b'A'..=b'Z' => Some(u32::from(item - b'A' + 27)),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -53,7 +186,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 8_072);
+        let Some(input) = real_input(3) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(3, 1, "input"));
     }
 
     #[test]
@@ -63,6 +197,78 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 2_567);
+        let Some(input) = real_input(3) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(3, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("vJrwpWtwJgWr\nhcsFMM1fFhFp\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.found, "`1`");
+
+        let error = try_part_1("vJrwpWtwJgWrh\n").unwrap_err();
+        assert_eq!(error.expected, "an even number of items");
+
+        let error = try_part_1("abcd\n").unwrap_err();
+        assert_eq!(
+            error.expected,
+            "an item in both compartments of the rucksack"
+        );
+
+        let error = try_part_2("aa\nab\nac\nbb\n").unwrap_err();
+        assert_eq!(error.found, "end of input");
+
+        let error = try_part_2("aa\nab\ncc\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    fn from_reader_test() {
+        for input in [SAMPLE_INPUT, &generator::generate(10_000, 1)] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), try_part_2(input));
+        }
+
+        let Err(ReadError::Parse(error)) = part_2_from_reader("aa\nab\ncc\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let Err(ReadError::Parse(error)) = part_2_from_reader("aa\nab\nac\nbb\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.found, "end of input");
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+            assert_eq!(
+                part_1_from_reader(input.as_bytes()).unwrap(),
+                part_1(SAMPLE_INPUT)
+            );
+            assert_eq!(
+                part_2_from_reader(input.as_bytes()).unwrap(),
+                part_2(SAMPLE_INPUT)
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day03>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day03>(&input);
+        }
     }
 }
@@ -0,0 +1,33 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+/// Generates about `size` bytes of section assignments, one random pair of ranges per line.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 16);
+    while input.len() < size {
+        let [first, second] = [(); 2].map(|()| {
+            let start = rng.range(1..=99);
+            (start, rng.range(start..=99))
+        });
+        writeln!(input, "{}-{},{}-{}", first.0, first.1, second.0, second.1).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day04;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((10_000..10_016).contains(&input.len()));
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day04::try_part_1(&input).is_ok());
+        assert!(Day04::try_part_2(&input).is_ok());
+    }
+}
@@ -1,60 +1,155 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
-use std::ops::RangeInclusive;
+pub mod generator;
+
+use common::{
+    stream::{LineReader, ReadError},
+    ParseError, Solution,
+};
+use std::{io::BufRead, ops::RangeInclusive};
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    input
-        .lines()
-        .filter(|line| {
-            let (first, second) = parse_section_assignment_pairs(line);
-            let left_contains_right =
-                first.start() <= second.start() && first.end() >= second.end();
-            let right_contains_left =
-                second.start() <= first.start() && second.end() >= first.end();
-            left_contains_right || right_contains_left
-        })
-        .count()
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    input
-        .lines()
-        .filter(|line| {
-            let (first, second) = parse_section_assignment_pairs(line);
-            first
-                .into_iter()
-                .any(|section_id| second.contains(&section_id))
-            // overlap(&left_section, &right_section) || overlap(&right_section, &left_section)
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not a pair of section assignments like `2-4,6-8`.
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Day04::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not a pair of section assignments like `2-4,6-8`.
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Day04::try_part_2(input)
+}
+
+/// Solves part 1 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    count_pairs_from_reader(reader, either_contains_other)
+}
+
+/// Solves part 2 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    count_pairs_from_reader(reader, overlap)
+}
+
+fn count_pairs_from_reader(
+    reader: impl BufRead,
+    predicate: fn(&RangeInclusive<u8>, &RangeInclusive<u8>) -> bool,
+) -> Result<usize, ReadError> {
+    LineReader::new(reader)
+        .parse_lines(|line| {
+            let (first, second) = parse_section_assignment_pairs(line, line)?;
+            Ok(predicate(&first, &second))
         })
+        .try_fold(0, |count, matches| Ok(count + usize::from(matches?)))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Parsed<'a> = Vec<(RangeInclusive<u8>, RangeInclusive<u8>)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(RangeInclusive<u8>, RangeInclusive<u8>)>, ParseError> {
+        input
+            .lines()
+            .map(|line| parse_section_assignment_pairs(input, line))
+            .collect()
+    }
+
+    fn solve_1(
+        _input: &str,
+        pairs: &Vec<(RangeInclusive<u8>, RangeInclusive<u8>)>,
+    ) -> Result<usize, ParseError> {
+        Ok(count_pairs(pairs, either_contains_other))
+    }
+
+    fn solve_2(
+        _input: &str,
+        pairs: &Vec<(RangeInclusive<u8>, RangeInclusive<u8>)>,
+    ) -> Result<usize, ParseError> {
+        Ok(count_pairs(pairs, overlap))
+    }
+}
+
+fn either_contains_other(first: &RangeInclusive<u8>, second: &RangeInclusive<u8>) -> bool {
+    let left_contains_right = first.start() <= second.start() && first.end() >= second.end();
+    let right_contains_left = second.start() <= first.start() && second.end() >= first.end();
+    left_contains_right || right_contains_left
+}
+
+fn overlap(first: &RangeInclusive<u8>, second: &RangeInclusive<u8>) -> bool {
+    first.clone().any(|section_id| second.contains(&section_id))
+}
+
+fn count_pairs(
+    pairs: &[(RangeInclusive<u8>, RangeInclusive<u8>)],
+    mut predicate: impl FnMut(&RangeInclusive<u8>, &RangeInclusive<u8>) -> bool,
+) -> usize {
+    pairs
+        .iter()
+        .filter(|(first, second)| predicate(first, second))
         .count()
 }
 
-fn parse_section_assignment_pairs(line: &str) -> (RangeInclusive<u8>, RangeInclusive<u8>) {
-    let (left, right) = line.split_once(',').unwrap();
-    (
-        parse_section_assignment(left),
-        parse_section_assignment(right),
-    )
+fn parse_section_assignment_pairs(
+    input: &str,
+    line: &str,
+) -> Result<(RangeInclusive<u8>, RangeInclusive<u8>), ParseError> {
+    let (left, right) = line
+        .split_once(',')
+        .ok_or_else(|| ParseError::at(input, &line[line.len()..], "`,`"))?;
+    Ok((
+        parse_section_assignment(input, left)?,
+        parse_section_assignment(input, right)?,
+    ))
 }
 
-fn parse_section_assignment(input: &str) -> RangeInclusive<u8> {
-    let (start, end) = input.split_once('-').unwrap();
-    start.parse().unwrap()..=end.parse().unwrap()
+fn parse_section_assignment(
+    input: &str,
+    assignment: &str,
+) -> Result<RangeInclusive<u8>, ParseError> {
+    let (start, end) = assignment
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(input, &assignment[assignment.len()..], "`-`"))?;
+    Ok(parse_section_id(input, start)?..=parse_section_id(input, end)?)
 }
 
-// fn overlap(left: &RangeInclusive<u8>, right: &RangeInclusive<u8>) -> bool {
-//     left.start() <= right.start() && left.end() <= right.start()
-// }
+fn parse_section_id(input: &str, id: &str) -> Result<u8, ParseError> {
+    id.parse()
+        .map_err(|_| ParseError::at(input, id, "a section ID from 0 to 255"))
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, Answer};
+    use proptest::prelude::*;
+    use std::collections::HashSet;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -63,7 +158,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 599);
+        let Some(input) = real_input(4) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(4, 1, "input"));
     }
 
     #[test]
@@ -73,6 +169,102 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 928);
+        let Some(input) = real_input(4) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(4, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("2-4,6-8\n2-3;4-5\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 8));
+        assert_eq!(error.expected, "`,`");
+        assert_eq!(error.found, "end of line");
+
+        let error = try_part_2("2-4,6-8\n2-3,45\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.expected, "`-`");
+
+        let error = try_part_1("2-4,6-800\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.found, "`800`");
+
+        let error = try_part_2("2-4,-8\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.found, "`-`");
+    }
+
+    #[test]
+    fn from_reader_test() {
+        for input in [SAMPLE_INPUT, &generator::generate(10_000, 1)] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), try_part_2(input));
+        }
+
+        let Err(ReadError::Parse(error)) = part_1_from_reader("2-4,6-8\n2-3,4\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.found, "end of line");
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+            assert_eq!(
+                part_1_from_reader(input.as_bytes()).unwrap(),
+                part_1(SAMPLE_INPUT)
+            );
+            assert_eq!(
+                part_2_from_reader(input.as_bytes()).unwrap(),
+                part_2(SAMPLE_INPUT)
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day04>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day04>(&input);
+        }
+
+        #[test]
+        fn reference_test(pairs in prop::collection::vec(
+            ((1..100_u8, 0..10_u8), (1..100_u8, 0..10_u8)),
+            1..50,
+        )) {
+            let sections = |(start, length): (u8, u8)| -> HashSet<u8> {
+                (start..=start + length).collect()
+            };
+            let input = pairs
+                .iter()
+                .map(|&((a, a_length), (b, b_length))| {
+                    format!("{a}-{},{b}-{}\n", a + a_length, b + b_length)
+                })
+                .collect::<Vec<_>>()
+                .concat();
+            let pairs: Vec<_> = pairs
+                .into_iter()
+                .map(|(first, second)| (sections(first), sections(second)))
+                .collect();
+            let contained = pairs
+                .iter()
+                .filter(|(first, second)| first.is_subset(second) || second.is_subset(first))
+                .count();
+            let overlapping = pairs
+                .iter()
+                .filter(|(first, second)| !first.is_disjoint(second))
+                .count();
+            prop_assert_eq!(try_part_1(&input), Ok(contained));
+            prop_assert_eq!(try_part_2(&input), Ok(overlapping));
+        }
     }
 }
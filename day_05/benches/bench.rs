@@ -0,0 +1,47 @@
+use common::input;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day_05::{generator, part_1, part_2};
+
+/// The real puzzle input, if it is there. Without it, only the generated inputs are measured.
+fn load_input() -> Option<String> {
+    input::load_input(5)
+        .map_err(|error| eprintln!("skipping benchmark: {error}"))
+        .ok()
+}
+
+fn part_1_benchmark(c: &mut Criterion) {
+    let Some(input) = load_input() else { return };
+    c.bench_function("part_1", |b| b.iter(|| part_1(black_box(&input))));
+}
+
+fn part_2_benchmark(c: &mut Criterion) {
+    let Some(input) = load_input() else { return };
+    c.bench_function("part_2", |b| b.iter(|| part_2(black_box(&input))));
+}
+
+/// The approximate sizes in bytes of the generated inputs, to measure how the solution scales.
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+fn scaling_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scaling");
+    group.sample_size(10);
+    for size in SIZES {
+        let input = generator::generate(size, 2022);
+        group.throughput(Throughput::Bytes(u64::try_from(input.len()).unwrap()));
+        group.bench_with_input(BenchmarkId::new("part_1", size), &input, |b, input| {
+            b.iter(|| part_1(black_box(input)));
+        });
+        group.bench_with_input(BenchmarkId::new("part_2", size), &input, |b, input| {
+            b.iter(|| part_2(black_box(input)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    part_1_benchmark,
+    part_2_benchmark,
+    scaling_benchmark
+);
+criterion_main!(benches);
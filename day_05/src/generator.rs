@@ -0,0 +1,63 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+const NUM_STACKS: usize = 9;
+
+/// Generates a drawing of nine stacks of up to eight crates, followed by about `size` bytes of
+/// steps. Every step moves at least one crate, and never more than its stack holds.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut heights = [0; NUM_STACKS];
+    for height in &mut heights {
+        *height = rng.range(1..=8);
+    }
+
+    let mut input = String::with_capacity(size + 300);
+    for row in (0..8).rev() {
+        let line: Vec<_> = heights
+            .iter()
+            .map(|&height| {
+                if height > row {
+                    format!("[{}]", char::from(rng.range(b'A'..=b'Z')))
+                } else {
+                    String::from("   ")
+                }
+            })
+            .collect();
+        writeln!(input, "{}", line.join(" ")).unwrap();
+    }
+    let numbers: Vec<_> = (1..=NUM_STACKS).map(|stack| format!(" {stack} ")).collect();
+    writeln!(input, "{}\n", numbers.join(" ")).unwrap();
+
+    let steps_start = input.len();
+    while input.len() - steps_start < size {
+        let from = loop {
+            let from = rng.range(0..=NUM_STACKS - 1);
+            if heights[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + rng.range(1..=NUM_STACKS - 1)) % NUM_STACKS;
+        let num_crates = rng.range(1..=heights[from]);
+        heights[from] -= num_crates;
+        heights[to] += num_crates;
+        writeln!(input, "move {num_crates} from {} to {}", from + 1, to + 1).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day05;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert_eq!(input, generate(10_000, 1));
+        assert_eq!(Day05::try_part_1(&input).unwrap().len(), NUM_STACKS);
+        assert!(Day05::try_part_2(&input).is_ok());
+    }
+}
@@ -1,99 +1,227 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod generator;
+#[cfg(feature = "visualize")]
+mod visualize;
+
+use common::{ParseError, Solution};
+
 type Stacks = Vec<Vec<char>>;
 type Steps = Vec<(u8, usize, usize)>;
 
 #[must_use]
 pub fn part_1(input: &str) -> String {
-    let (mut stacks, steps) = parse_input(input);
-    crate_mover_9000(steps, &mut stacks);
-    top_items(stacks)
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> String {
-    let (mut stacks, steps) = parse_input(input);
-    crate_mover_9001(steps, &mut stacks);
-    top_items(stacks)
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if the drawing of the stacks or one of the steps cannot be parsed, or if a
+/// step moves more crates than its stack holds.
+pub fn try_part_1(input: &str) -> Result<String, ParseError> {
+    Day05::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the drawing of the stacks or one of the steps cannot be parsed, or if a
+/// step moves more crates than its stack holds.
+pub fn try_part_2(input: &str) -> Result<String, ParseError> {
+    Day05::try_part_2(input)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed<'a> = (Stacks, Steps);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<(Stacks, Steps), ParseError> {
+        parse_input(input)
+    }
+
+    fn solve_1(_input: &str, (stacks, steps): &(Stacks, Steps)) -> Result<String, ParseError> {
+        let mut stacks = stacks.clone();
+        crate_mover_9000(steps, &mut stacks);
+        Ok(top_items(&stacks))
+    }
+
+    fn solve_2(_input: &str, (stacks, steps): &(Stacks, Steps)) -> Result<String, ParseError> {
+        let mut stacks = stacks.clone();
+        crate_mover_9001(steps, &mut stacks);
+        Ok(top_items(&stacks))
+    }
 }
 
-fn parse_input(input: &str) -> (Stacks, Steps) {
-    let (stacks_input, steps_input) = input.split_once("\n\n").unwrap();
-    (parse_stacks(stacks_input), parse_steps(steps_input))
+fn parse_input(input: &str) -> Result<(Stacks, Steps), ParseError> {
+    let (stacks_input, steps_input) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::at(
+            input,
+            &input[input.len()..],
+            "a blank line before the steps",
+        )
+    })?;
+    let stacks = parse_stacks(input, stacks_input)?;
+    let steps = parse_steps(input, steps_input, &stacks)?;
+    Ok((stacks, steps))
 }
 
-fn parse_stacks(stacks_input: &str) -> Vec<Vec<char>> {
+fn parse_stacks(input: &str, stacks_input: &str) -> Result<Stacks, ParseError> {
     let mut stacks_lines: Vec<&str> = stacks_input.lines().collect();
-    let last_line = stacks_lines.pop().unwrap();
-    let num_stacks: usize = (last_line.as_bytes()[last_line.len() - 2] - b'0') as usize;
+    let last_line = stacks_lines
+        .pop()
+        .ok_or_else(|| ParseError::at(input, stacks_input, "a drawing of the stacks"))?;
+    let num_stacks = parse_stack_numbers(input, last_line)?;
     let mut stacks: Vec<Vec<char>> = vec![Vec::new(); num_stacks];
-    for line in stacks_lines.into_iter().rev() {
+    for (height, line) in stacks_lines.into_iter().rev().enumerate() {
+        if let Some(i) = line.find(|c: char| !c.is_ascii()) {
+            return Err(ParseError::at_char(input, &line[i..], "a crate or a space"));
+        }
+        let drawing_width = num_stacks * 4 - 1;
+        if line.len() > drawing_width && !line[drawing_width..].trim().is_empty() {
+            let rest = line[drawing_width..].trim_start();
+            return Err(ParseError::at_char(input, rest, "end of line"));
+        }
         for (i, stack) in stacks.iter_mut().enumerate() {
-            match line.as_bytes()[1 + i * 4] {
-                byte @ b'A'..=b'Z' => stack.push(byte as char),
-                b' ' => (),
-                byte => panic!(
-                    "Unexpected character: '{}' in line: \"{}\"",
-                    byte as char, line
-                ),
+            match line.as_bytes().get(1 + i * 4) {
+                Some(_) if stack.len() < height && line[i * 4..].starts_with('[') => {
+                    let item = &line[i * 4..line.len().min(i * 4 + 3)];
+                    return Err(ParseError::at(input, item, "no crate above an empty slot"));
+                }
+                Some(byte @ b'A'..=b'Z') => stack.push(*byte as char),
+                Some(b' ') | None => (),
+                Some(_) => {
+                    return Err(ParseError::at_char(
+                        input,
+                        &line[1 + i * 4..],
+                        "a crate from `A` to `Z` or a space",
+                    ))
+                }
             }
         }
     }
-    stacks
+    Ok(stacks)
+}
+
+/// Parses the line below the drawing, which numbers the stacks from 1.
+fn parse_stack_numbers(input: &str, line: &str) -> Result<usize, ParseError> {
+    let mut num_stacks = 0;
+    for number in line.split_whitespace() {
+        num_stacks += 1;
+        if number.parse() != Ok(num_stacks) {
+            return Err(ParseError::at(
+                input,
+                number,
+                format!("stack number {num_stacks}"),
+            ));
+        }
+    }
+    if num_stacks == 0 {
+        Err(ParseError::at(input, line, "the numbers of the stacks"))
+    } else {
+        Ok(num_stacks)
+    }
 }
 
-fn parse_steps(steps_input: &str) -> Vec<(u8, usize, usize)> {
-    let steps: Vec<(u8, usize, usize)> = steps_input
+/// Parses the steps, checking that none of them takes more crates from a stack than it holds.
+fn parse_steps(input: &str, steps_input: &str, stacks: &Stacks) -> Result<Steps, ParseError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    steps_input
         .lines()
-        .map(|mut line| {
-            line = line.strip_prefix("move ").unwrap();
-            let (num_items, line) = line.split_once(" from ").unwrap();
-            let (from, to) = line.split_once(" to ").unwrap();
-            (
-                num_items.parse().unwrap(),
-                from.parse().unwrap(),
-                to.parse().unwrap(),
-            )
+        .map(|line| {
+            let words: Vec<&str> = line.split(' ').collect();
+            let word = |i: usize| words.get(i).copied().unwrap_or(&line[line.len()..]);
+            for (i, keyword) in [(0, "move"), (2, "from"), (4, "to")] {
+                if word(i) != keyword {
+                    return Err(ParseError::at(input, word(i), format!("`{keyword}`")));
+                }
+            }
+            if words.len() > 6 {
+                return Err(ParseError::at(input, word(6), "end of line"));
+            }
+
+            let num_items: u8 = word(1)
+                .parse()
+                .map_err(|_| ParseError::at(input, word(1), "a number of crates up to 255"))?;
+            let from = parse_stack(input, word(3), heights.len())?;
+            let to = parse_stack(input, word(5), heights.len())?;
+            if heights[from - 1] < usize::from(num_items) {
+                let expected = format!("at most {} crates to move", heights[from - 1]);
+                return Err(ParseError::at(input, word(1), expected));
+            }
+            heights[from - 1] -= usize::from(num_items);
+            heights[to - 1] += usize::from(num_items);
+            Ok((num_items, from, to))
         })
-        .collect();
-    steps
+        .collect()
 }
 
-fn crate_mover_9000(steps: Vec<(u8, usize, usize)>, stacks: &mut [Vec<char>]) {
-    for (num_items, from, to) in steps {
-        for _ in 0..num_items {
-            let item = stacks[from - 1].pop().unwrap();
-            stacks[to - 1].push(item);
-        }
+fn parse_stack(input: &str, word: &str, num_stacks: usize) -> Result<usize, ParseError> {
+    match word.parse() {
+        Ok(stack) if (1..=num_stacks).contains(&stack) => Ok(stack),
+        _ => Err(ParseError::at(
+            input,
+            word,
+            format!("a stack from 1 to {num_stacks}"),
+        )),
     }
 }
 
-fn crate_mover_9001(steps: Vec<(u8, usize, usize)>, stacks: &mut [Vec<char>]) {
-    let mut crane_stack = Vec::new();
-    for (num_items, from, to) in steps {
-        for _ in 0..num_items {
-            crane_stack.push(stacks[from - 1].pop().unwrap());
-        }
-        while let Some(item) = crane_stack.pop() {
-            stacks[to - 1].push(item);
-        }
+fn crate_mover_9000(steps: &[(u8, usize, usize)], stacks: &mut [Vec<char>]) {
+    for step in steps {
+        move_one_at_a_time(step, stacks);
     }
 }
 
-fn top_items(stacks: Vec<Vec<char>>) -> String {
+fn crate_mover_9001(steps: &[(u8, usize, usize)], stacks: &mut [Vec<char>]) {
+    for step in steps {
+        move_all_at_once(step, stacks);
+    }
+}
+
+fn move_one_at_a_time(&(num_items, from, to): &(u8, usize, usize), stacks: &mut [Vec<char>]) {
+    for _ in 0..num_items {
+        let item = stacks[from - 1].pop().unwrap();
+        common::trace!("move", crates = item, from = from, to = to);
+        stacks[to - 1].push(item);
+    }
+}
+
+fn move_all_at_once(&(num_items, from, to): &(u8, usize, usize), stacks: &mut [Vec<char>]) {
+    let from_stack = &mut stacks[from - 1];
+    let items = from_stack.split_off(from_stack.len() - usize::from(num_items));
+    common::trace!(
+        "move",
+        crates = items.iter().collect::<String>(),
+        from = from,
+        to = to
+    );
+    stacks[to - 1].extend(items);
+}
+
+fn top_items(stacks: &[Vec<char>]) -> String {
     stacks
-        .into_iter()
-        .map(|stack| *stack.last().unwrap())
+        .iter()
+        .map(|stack| stack.last().copied().unwrap_or(' '))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -102,7 +230,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), String::from("ZRLJGSCTR"));
+        let Some(input) = real_input(5) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(5, 1, "input"));
     }
 
     #[test]
@@ -112,6 +241,76 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), String::from("PRTTGRFPB"));
+        let Some(input) = real_input(5) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(5, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("[A]\n 1 \nmove 1 from 1 to 1\n").unwrap_err();
+        assert_eq!(error.found, "end of input");
+
+        let error = try_part_1("[A] [b]\n 1   2 \n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert_eq!(error.found, "`b`");
+
+        let error = try_part_2("[A] [B]\n 1   3 \n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.expected, "stack number 2");
+
+        let error = try_part_1("    [C]\n[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.found, "`[C]`");
+
+        let error = try_part_2("[A] [B]\n 1   2 \n\nmove 1 from 1 to 3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 18));
+        assert_eq!(error.expected, "a stack from 1 to 2");
+
+        let error =
+            try_part_1("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 2 from 1 to 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 6));
+        assert_eq!(error.expected, "at most 0 crates to move");
+
+        let error = try_part_2("[A] [B]\n 1   2 \n\nmove 1 fro 1 to 2\n").unwrap_err();
+        assert_eq!(error.found, "`fro`");
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let (_, events) = trace::collect(|| part_1(SAMPLE_INPUT));
+        assert_eq!(events.len(), 7);
+
+        let (answer, events) = trace::collect(|| part_2(SAMPLE_INPUT));
+        assert_eq!(answer, "MCD");
+        let moves: Vec<_> = events.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            moves,
+            [
+                r#"move crates="D" from=2 to=1"#,
+                r#"move crates="ZND" from=1 to=3"#,
+                r#"move crates="MC" from=2 to=1"#,
+                r#"move crates="C" from=1 to=2"#,
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day05>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day05>(&input);
+        }
     }
 }
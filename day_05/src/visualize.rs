@@ -0,0 +1,91 @@
+use crate::{move_all_at_once, move_one_at_a_time, parse_input, Day05};
+use common::{
+    visualize::{Color, Frame, Visualize},
+    ParseError,
+};
+
+impl Visualize for Day05 {
+    /// Draws the stacks before the first step and after every step, with the crates that were
+    /// just moved highlighted.
+    fn frames(part: u8, input: &str) -> Result<Vec<Frame>, ParseError> {
+        let (mut stacks, steps) = parse_input(input)?;
+        let move_crates = if part == 1 {
+            move_one_at_a_time
+        } else {
+            move_all_at_once
+        };
+        let mut states = vec![(stacks.clone(), None)];
+        for step in &steps {
+            move_crates(step, &mut stacks);
+            states.push((stacks.clone(), Some(*step)));
+        }
+        let max_height = states
+            .iter()
+            .flat_map(|(stacks, _)| stacks.iter().map(Vec::len))
+            .max()
+            .unwrap_or(0);
+        Ok(states
+            .iter()
+            .map(|(stacks, step)| draw(stacks, *step, max_height))
+            .collect())
+    }
+}
+
+fn draw(stacks: &[Vec<char>], step: Option<(u8, usize, usize)>, max_height: usize) -> Frame {
+    let caption = match step {
+        Some((num_items, from, to)) => format!("move {num_items} from {from} to {to}"),
+        None => String::from("starting stacks"),
+    };
+    let mut frame = Frame::new(max_height + 1, stacks.len() * 4 - 1).with_caption(caption);
+    for (i, stack) in stacks.iter().enumerate() {
+        let num_moved = match step {
+            Some((num_items, _, to)) if to == i + 1 => usize::from(num_items),
+            _ => 0,
+        };
+        for (height, item) in stack.iter().enumerate() {
+            let color = if height < stack.len() - num_moved {
+                Color::Default
+            } else {
+                Color::Yellow
+            };
+            frame.write(max_height - 1 - height, i * 4, &format!("[{item}]"), color);
+        }
+        frame.write(max_height, i * 4 + 1, &(i + 1).to_string(), Color::Dim);
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::visualize::{Ascii, Renderer};
+
+    #[test]
+    fn test_frames() {
+        let input = include_str!("../sample-input.txt");
+        let frames = Day05::frames(2, input).unwrap();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(
+            Ascii.render(&frames[0]),
+            "
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+starting stacks
+"
+        );
+        assert_eq!(frames[2].get(0, 9).color, Color::Yellow);
+        assert_eq!(frames[2].get(3, 9).color, Color::Default);
+        assert_eq!(
+            Ascii.render(&frames[4]),
+            "        [D]
+        [N]
+        [Z]
+[M] [C] [P]
+ 1   2   3
+move 1 from 1 to 2
+"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use common::rng::Rng;
+
+/// Generates a datastream of `size` bytes, at least 14, with both markers right at the end, so
+/// that finding them takes a scan of the whole stream. Before the markers, only three different
+/// characters are used, so no sequence of four different characters can start there.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut markers: Vec<u8> = (b'd'..=b'z').collect();
+    rng.shuffle(&mut markers);
+    markers.truncate(14);
+
+    let mut input: String = (0..size.max(14) - 14)
+        .map(|_| char::from(*rng.choose(b"abc")))
+        .collect();
+    input.extend(markers.into_iter().map(char::from));
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day06;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert_eq!(input.len(), 10_000);
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day06::try_part_1(&input).unwrap() > 9_980);
+        assert!(Day06::try_part_2(&input).unwrap() > 9_980);
+    }
+}
@@ -1,17 +1,123 @@
 #![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)]
+
+pub mod generator;
+
+use common::{
+    stream::{NormalizedBytes, ReadError},
+    ParseError, Solution,
+};
+use std::{collections::VecDeque, io::BufRead};
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    find_unique_sequence(input.as_bytes(), 4)
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    find_unique_sequence(input.as_bytes(), 14)
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if the buffer has no start-of-packet marker.
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Day06::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the buffer has no start-of-message marker.
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Day06::try_part_2(input)
 }
 
-fn find_unique_sequence(buffer: &[u8], length: usize) -> usize {
-    buffer.windows(length).position(all_bytes_unique).unwrap() + length
+/// Solves part 1 while reading the input, stopping at the marker.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or has no start-of-packet marker.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    find_unique_sequence_from_reader(reader, 4)
+}
+
+/// Solves part 2 while reading the input, stopping at the marker.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or has no start-of-message marker.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    find_unique_sequence_from_reader(reader, 14)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Parsed<'a> = &'a [u8];
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<&[u8], ParseError> {
+        Ok(input.as_bytes())
+    }
+
+    fn solve_1(input: &str, buffer: &&[u8]) -> Result<usize, ParseError> {
+        find_unique_sequence(input, buffer, 4)
+    }
+
+    fn solve_2(input: &str, buffer: &&[u8]) -> Result<usize, ParseError> {
+        find_unique_sequence(input, buffer, 14)
+    }
+}
+
+fn find_unique_sequence(input: &str, buffer: &[u8], length: usize) -> Result<usize, ParseError> {
+    buffer
+        .windows(length)
+        .position(all_bytes_unique)
+        .map(|position| position + length)
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                &input[input.len()..],
+                format!("a sequence of {length} different characters"),
+            )
+        })
+}
+
+/// Finds the marker like [`find_unique_sequence`], keeping only the last `length` bytes in memory.
+/// The error when there is no marker points at the end of the input, but without the text of its
+/// last line.
+fn find_unique_sequence_from_reader(
+    reader: impl BufRead,
+    length: usize,
+) -> Result<usize, ReadError> {
+    let mut window = VecDeque::with_capacity(length);
+    let (mut line, mut column) = (1, 1);
+    for (position, byte) in NormalizedBytes::new(reader).enumerate() {
+        let byte = byte?;
+        if window.len() == length {
+            window.pop_front();
+        }
+        window.push_back(byte);
+        if window.len() == length && all_bytes_unique(window.make_contiguous()) {
+            return Ok(position + 1);
+        }
+        if byte == b'\n' {
+            line += 1;
+            column = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Only the first byte of each character moves to the next column.
+            column += 1;
+        }
+    }
+
+    let expected = format!("a sequence of {length} different characters");
+    let mut error = ParseError::at("", "", expected);
+    error.line = line;
+    error.column = column;
+    Err(ReadError::Parse(error))
 }
 
 fn all_bytes_unique(sequence: &[u8]) -> bool {
@@ -24,8 +130,10 @@ fn all_bytes_unique(sequence: &[u8]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = include_str!("../input.txt");
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, Answer};
+    use proptest::prelude::*;
+    use std::collections::HashSet;
 
     #[test]
     fn part_1_sample_test() {
@@ -38,7 +146,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 1_042);
+        let Some(input) = real_input(6) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(6, 1, "input"));
     }
 
     #[test]
@@ -52,6 +161,119 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 2_980);
+        let Some(input) = real_input(6) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(6, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("abcabcabc").unwrap_err();
+        assert_eq!(error.found, "end of input");
+        assert_eq!(error.expected, "a sequence of 4 different characters");
+
+        let error = try_part_2("mjqjpqmgbljsphdztnvjfqwrcgsmlb".split_at(18).0).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 19));
+    }
+
+    #[test]
+    fn from_reader_test() {
+        for input in [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            &generator::generate(10_000, 1),
+        ] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), try_part_2(input));
+        }
+
+        for input in ["abcabc", "aba\naba\n"] {
+            let Err(ReadError::Parse(error)) = part_1_from_reader(input.as_bytes()) else {
+                panic!("expected a parse error");
+            };
+            let expected = try_part_1(input).unwrap_err();
+            assert_eq!((error.line, error.column), (expected.line, expected.column));
+            assert_eq!(
+                (error.found, error.expected),
+                (expected.found, expected.expected)
+            );
+        }
+
+        let Err(ReadError::Io(error)) = part_1_from_reader(&b"ab\xFFcd"[..]) else {
+            panic!("expected an I/O error");
+        };
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    fn assert_same_from_reader(input: &str) {
+        for (length, solve) in [(4, try_part_1 as fn(&str) -> _), (14, try_part_2)] {
+            match (
+                find_unique_sequence_from_reader(input.as_bytes(), length),
+                solve(input),
+            ) {
+                (Ok(position), Ok(expected)) => assert_eq!(position, expected, "{input:?}"),
+                (Err(ReadError::Parse(error)), Err(expected)) => assert_eq!(
+                    (error.line, error.column),
+                    (expected.line, expected.column),
+                    "{input:?}"
+                ),
+                (result, expected) => panic!("{input:?}: {result:?} but {expected:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn from_reader_whitespace_test() {
+        assert_eq!(part_1_from_reader("aab\r\n".as_bytes()).ok(), None);
+        for input in [
+            "aab\r\n",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\r\n",
+            "abc \t\n\n\r\n",
+            "ab\n\n \ncdefgh",
+            "\n\nabc\u{3000}d",
+            " \t\u{a0}\u{3000}",
+        ] {
+            assert_same_from_reader(input);
+        }
+        for input in whitespace_variants("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n") {
+            assert_same_from_reader(&input);
+        }
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n") {
+            assert_eq!(part_1(&input), part_1("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"));
+            assert_eq!(part_2(&input), part_2("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n"));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day06>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day06>(&input);
+        }
+
+        #[test]
+        fn from_reader_reference_test(input in "[a-f \t\r\n\u{a0}]{0,40}") {
+            assert_same_from_reader(&input);
+        }
+
+        #[test]
+        fn reference_test(input in "[a-p]{0,100}") {
+            for (length, solve) in [(4, try_part_1 as fn(&str) -> _), (14, try_part_2)] {
+                let expected = input
+                    .as_bytes()
+                    .windows(length)
+                    .position(|window| window.iter().collect::<HashSet<_>>().len() == length)
+                    .map(|position| position + length);
+                prop_assert_eq!(solve(&input).ok(), expected);
+            }
+        }
     }
 }
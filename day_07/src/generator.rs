@@ -0,0 +1,83 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+/// The directory tree is at most this deep.
+const MAX_DEPTH: usize = 20;
+
+/// Generates a terminal transcript of about `size` bytes, which explores a random directory tree
+/// depth first. The file sizes are kept small enough for the total to fit in 32 bits.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 1_000);
+    let mut space_left = 4_000_000_000;
+    input.push_str("$ cd /\n");
+    explore(&mut rng, &mut input, size, 0, &mut space_left);
+    input
+}
+
+/// Lists a directory and then explores its subdirectories. A directory gets subdirectories as
+/// long as the transcript is shorter than `size`.
+fn explore(rng: &mut Rng, input: &mut String, size: usize, depth: usize, space_left: &mut u32) {
+    let num_directories = if input.len() < size && depth < MAX_DEPTH {
+        rng.range(1..=4)
+    } else {
+        0
+    };
+    let mut names: Vec<String> = Vec::new();
+    while names.len() < num_directories + rng.range(1..=5) {
+        let name = random_name(rng);
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let (directories, files) = names.split_at(num_directories);
+
+    let mut entries: Vec<String> = directories
+        .iter()
+        .map(|name| format!("dir {name}"))
+        .collect();
+    for name in files {
+        let file_size = rng.range(1..=300_000).min(*space_left);
+        *space_left -= file_size;
+        let extension = if rng.percent(50) {
+            format!(".{}", random_name(rng))
+        } else {
+            String::new()
+        };
+        entries.push(format!("{file_size} {name}{extension}"));
+    }
+    entries.sort_unstable();
+
+    input.push_str("$ ls\n");
+    for entry in entries {
+        writeln!(input, "{entry}").unwrap();
+    }
+    for name in directories {
+        writeln!(input, "$ cd {name}").unwrap();
+        explore(rng, input, size, depth + 1, space_left);
+        input.push_str("$ cd ..\n");
+    }
+}
+
+fn random_name(rng: &mut Rng) -> String {
+    (0..rng.range(1..=8))
+        .map(|_| char::from(rng.range(b'a'..=b'z')))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day07;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!(input.len() >= 10_000);
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day07::try_part_1(&input).is_ok());
+        assert!(Day07::try_part_2(&generate(100_000, 2)).is_ok());
+    }
+}
@@ -1,68 +1,153 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod generator;
+
+use common::{ParseError, Solution};
+
 #[must_use]
 pub fn part_1(input: &str) -> u32 {
-    calculate_directory_sizes(input)
-        .iter()
-        .filter_map(|directory| {
-            if directory.size <= 100_000 {
-                Some(directory.size)
-            } else {
-                None
-            }
-        })
-        .sum()
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u32 {
-    let directories = calculate_directory_sizes(input);
-    let needed_space = 30_000_000 - (70_000_000 - directories[0].size);
-    directories
-        .iter()
-        .filter_map(|directory| {
-            if directory.size >= needed_space {
-                Some(directory.size)
-            } else {
-                None
-            }
-        })
-        .min()
-        .unwrap()
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a line of the terminal output cannot be parsed or if it changes into a
+/// directory that has not been listed.
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Day07::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a line of the terminal output cannot be parsed or if it changes into a
+/// directory that has not been listed.
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Day07::try_part_2(input)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    /// The total size of every directory, starting with the root directory.
+    type Parsed<'a> = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        Ok(calculate_directory_sizes(input)?
+            .iter()
+            .map(|directory| directory.size)
+            .collect())
+    }
+
+    fn solve_1(_input: &str, sizes: &Vec<u32>) -> Result<u32, ParseError> {
+        Ok(sizes
+            .iter()
+            .filter_map(|&size| if size <= 100_000 { Some(size) } else { None })
+            .sum())
+    }
+
+    fn solve_2(_input: &str, sizes: &Vec<u32>) -> Result<u32, ParseError> {
+        let used_space = sizes[0];
+        let needed_space = 30_000_000_u32.saturating_sub(70_000_000_u32.saturating_sub(used_space));
+        Ok(sizes
+            .iter()
+            .filter_map(|&size| {
+                if size >= needed_space {
+                    Some(size)
+                } else {
+                    None
+                }
+            })
+            .min()
+            .unwrap_or(used_space))
+    }
 }
 
-fn calculate_directory_sizes(input: &str) -> Vec<Directory> {
+fn calculate_directory_sizes(input: &str) -> Result<Vec<Directory<'_>>, ParseError> {
     let mut directories = vec![Directory::new("/", 0)];
     let mut current_directory = 0;
 
-    for line in input.lines().skip(1) {
+    let mut lines = input.lines();
+    match lines.next() {
+        Some("$ cd /") => (),
+        line => {
+            let line = line.unwrap_or(&input[input.len()..]);
+            return Err(ParseError::at(input, line, "`$ cd /`"));
+        }
+    }
+
+    for line in lines {
         if line == "$ cd .." {
-            let parent = directories[current_directory].parent;
-            directories[parent].size += directories[current_directory].size;
-            current_directory = parent;
+            if current_directory == 0 {
+                return Err(ParseError::at(input, line, "a directory to move out of"));
+            }
+            current_directory = move_out(&mut directories, current_directory, input, line)?;
+        } else if line == "$ cd /" {
+            while current_directory != 0 {
+                current_directory = move_out(&mut directories, current_directory, input, line)?;
+            }
         } else if let Some(child) = line.strip_prefix("$ cd ") {
             current_directory = directories
                 .iter()
                 .position(|directory| {
                     directory.parent == current_directory && directory.name == child
                 })
-                .unwrap();
+                .ok_or_else(|| ParseError::at(input, child, "a directory listed by `ls`"))?;
+            common::trace!("cd", directory = child);
         } else if let Some(child) = line.strip_prefix("dir ") {
             directories.push(Directory::new(child, current_directory));
+        } else if line.starts_with('$') && line != "$ ls" {
+            return Err(ParseError::at(input, line, "`$ cd` or `$ ls`"));
         } else if line != "$ ls" {
-            directories[current_directory].size +=
-                line.split_once(' ').unwrap().0.parse::<u32>().unwrap();
+            let (size, _) = line.split_once(' ').ok_or_else(|| {
+                ParseError::at(input, &line[line.len()..], "a space before the file name")
+            })?;
+            let size = size
+                .parse::<u32>()
+                .map_err(|_| ParseError::at(input, size, "`dir` or a file size"))?;
+            let directory = &mut directories[current_directory];
+            directory.size = directory.size.checked_add(size).ok_or_else(|| {
+                ParseError::at(input, line, "a directory size that fits in 32 bits")
+            })?;
         }
     }
 
+    let end_of_input = &input[input.len()..];
     while current_directory != 0 {
-        let parent = directories[current_directory].parent;
-        directories[parent].size += directories[current_directory].size;
-        current_directory = parent;
+        current_directory = move_out(&mut directories, current_directory, input, end_of_input)?;
     }
 
-    directories
+    Ok(directories)
+}
+
+/// Adds the size of a directory to its parent's and returns the parent.
+fn move_out(
+    directories: &mut [Directory],
+    directory: usize,
+    input: &str,
+    line: &str,
+) -> Result<usize, ParseError> {
+    let parent = directories[directory].parent;
+    directories[parent].size = directories[parent]
+        .size
+        .checked_add(directories[directory].size)
+        .ok_or_else(|| ParseError::at(input, line, "a directory size that fits in 32 bits"))?;
+    common::trace!(
+        "move_out",
+        directory = directories[directory].name,
+        size = directories[directory].size,
+        parent = directories[parent].name,
+        parent_size = directories[parent].size
+    );
+    Ok(parent)
 }
 
 struct Directory<'a> {
@@ -84,9 +169,11 @@ impl<'a> Directory<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -95,7 +182,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 1_443_806);
+        let Some(input) = real_input(7) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(7, 1, "input"));
     }
 
     #[test]
@@ -105,6 +193,71 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 942_298);
+        let Some(input) = real_input(7) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(7, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("$ ls\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "`$ cd /`");
+
+        let error = try_part_1("$ cd /\n$ ls\ndir a\n$ cd b\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 6));
+        assert_eq!(error.found, "`b`");
+
+        let error = try_part_2("$ cd /\n$ cd ..\n").unwrap_err();
+        assert_eq!(error.line, 2);
+
+        let error = try_part_1("$ cd /\n$ ls\n12x b.txt\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.found, "`12x`");
+
+        let error = try_part_1("$ cd /\n$ rm -rf a\n").unwrap_err();
+        assert_eq!(error.expected, "`$ cd` or `$ ls`");
+
+        let error = try_part_2("$ cd /\n$ ls\n4000000000 a\n4000000000 b\n").unwrap_err();
+        assert_eq!(error.line, 4);
+    }
+
+    #[test]
+    fn cd_to_root_test() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n10 b\n$ cd /\n$ ls\n5 c\n";
+        assert_eq!(part_1(input), 25);
+    }
+
+    #[test]
+    fn trace_test() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n10 b\n$ cd /\n$ ls\n5 c\n";
+        let (_, events) = trace::collect(|| part_1(input));
+        let events: Vec<_> = events.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            events,
+            [
+                r#"cd directory="a""#,
+                r#"move_out directory="a" size=10 parent="/" parent_size=10"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day07>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day07>(&input);
+        }
     }
 }
@@ -0,0 +1,30 @@
+use common::rng::Rng;
+
+/// Generates a square grid of about `size` bytes of random tree heights.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let width = size.isqrt().max(1);
+    let mut input = String::with_capacity((width + 1) * width);
+    for _ in 0..width {
+        input.extend((0..width).map(|_| char::from(rng.range(b'0'..=b'9'))));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day08;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert_eq!(input.len(), 100 * 101);
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day08::try_part_1(&input).unwrap() >= 4 * 99);
+        assert!(Day08::try_part_2(&input).is_ok());
+    }
+}
@@ -1,63 +1,106 @@
 #![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)]
 
-use std::{mem::replace, ops::Index};
+pub mod generator;
+#[cfg(feature = "visualize")]
+mod visualize;
+
+use common::{ParseError, Solution};
+use grid::{Direction, GridRef, Point};
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    Grove::from(input).count_visible_trees()
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    Grove::from(input).max_scenic_score()
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
-struct Grove<'a> {
-    trees: &'a [u8],
-    num_columns: usize,
-    num_rows: usize,
+/// # Errors
+///
+/// Returns an error if the input is not a rectangular grid of tree heights from 0 to 9.
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Day08::try_part_1(input)
 }
 
-impl Grove<'_> {
-    fn all_coordinates(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        (0..self.num_rows).flat_map(|row| (0..self.num_columns).map(move |column| (row, column)))
+/// # Errors
+///
+/// Returns an error if the input is not a rectangular grid of tree heights from 0 to 9.
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Day08::try_part_2(input)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Parsed<'a> = Grove<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grove<'_>, ParseError> {
+        Grove::try_from(input)
+    }
+
+    fn solve_1(_input: &str, grove: &Grove<'_>) -> Result<usize, ParseError> {
+        Ok(grove.count_visible_trees())
+    }
+
+    fn solve_2(_input: &str, grove: &Grove<'_>) -> Result<usize, ParseError> {
+        Ok(grove.max_scenic_score())
     }
+}
+
+pub struct Grove<'a> {
+    trees: GridRef<'a>,
+}
 
+impl Grove<'_> {
     fn count_visible_trees(&self) -> usize {
-        self.all_coordinates()
-            .filter(|&(row, column)| {
-                let tree_height = self[(row, column)];
-                // Check in every direction if there are no higher trees blocking the view.
-                (0..row).all(|r| self[(r, column)] < tree_height)
-                    || (row + 1..self.num_rows).all(|r| self[(r, column)] < tree_height)
-                    || (0..column).all(|c| self[(row, c)] < tree_height)
-                    || (column + 1..self.num_columns).all(|c| self[(row, c)] < tree_height)
-            })
+        self.trees
+            .points()
+            .filter(|&point| self.is_visible(point))
             .count()
     }
 
+    fn is_visible(&self, point: Point) -> bool {
+        let tree_height = self.trees[point];
+        // Check in every direction if there are no higher trees blocking the view.
+        Direction::ORTHOGONAL.into_iter().any(|direction| {
+            self.trees
+                .ray(point, direction)
+                .all(|other| self.trees[other] < tree_height)
+        })
+    }
+
     fn max_scenic_score(&self) -> usize {
-        self.all_coordinates()
-            .map(|(row, column)| {
-                // Count the trees in line of sight in every direction.
-                self.count_in_line_of_sight((0..=row).rev().map(|r| (r, column)))
-                    * self.count_in_line_of_sight((row..self.num_rows).map(|r| (r, column)))
-                    * self.count_in_line_of_sight((0..=column).rev().map(|c| (row, c)))
-                    * self.count_in_line_of_sight((column..self.num_columns).map(|c| (row, c)))
-            })
+        self.trees
+            .points()
+            .map(|point| self.scenic_score(point))
             .max()
             .unwrap()
     }
 
-    fn count_in_line_of_sight(&self, mut points: impl Iterator<Item = (usize, usize)>) -> usize {
-        let tree_height = self[points.next().unwrap()];
-        points
-            .take_while_inclusive(|&(row, column)| self[(row, column)] < tree_height)
+    fn scenic_score(&self, point: Point) -> usize {
+        // Count the trees in line of sight in every direction.
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(|direction| self.count_in_line_of_sight(point, direction))
+            .product()
+    }
+
+    fn count_in_line_of_sight(&self, point: Point, direction: Direction) -> usize {
+        let tree_height = self.trees[point];
+        self.trees
+            .ray(point, direction)
+            .take_while_inclusive(|&other| self.trees[other] < tree_height)
             .count()
         // let mut count = 0;
-        // for (row, column) in points {
+        // for other in self.trees.ray(point, direction) {
         //     count += 1;
-        //     if self[(row, column)] >= tree_height {
+        //     if self.trees[other] >= tree_height {
         //         break;
         //     }
         // }
@@ -65,24 +108,18 @@ impl Grove<'_> {
     }
 }
 
-impl<'a> From<&'a str> for Grove<'a> {
-    fn from(s: &'a str) -> Self {
-        let trees = s.as_bytes();
-        let num_columns = trees.iter().position(|&byte| byte == b'\n').unwrap();
-        let num_rows = trees.len() / num_columns;
-        Self {
-            trees,
-            num_columns,
-            num_rows,
-        }
-    }
-}
-
-impl Index<(usize, usize)> for Grove<'_> {
-    type Output = u8;
+impl<'a> TryFrom<&'a str> for Grove<'a> {
+    type Error = ParseError;
 
-    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
-        &self.trees[row * (self.num_columns + 1) + column]
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Ok(Self {
+            trees: GridRef::parse(
+                s,
+                |byte| byte.is_ascii_digit(),
+                "a tree height from 0 to 9",
+                "trees",
+            )?,
+        })
     }
 }
 
@@ -98,7 +135,7 @@ where
     I: Sized + Iterator,
     P: FnMut(&I::Item) -> bool,
 {
-    fn take_while_inclusive(mut self, predicate: P) -> TakeWhileInclusive<I, P> {
+    fn take_while_inclusive(self, predicate: P) -> TakeWhileInclusive<I, P> {
         TakeWhileInclusive {
             iterator: self,
             predicate,
@@ -128,7 +165,7 @@ where
             None
         } else if let Some(value) = self.iterator.next() {
             if !(self.predicate)(&value) {
-                self.flag = true
+                self.flag = true;
             }
             Some(value)
         } else {
@@ -140,9 +177,11 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -151,7 +190,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 1_845);
+        let Some(input) = real_input(8) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(8, 1, "input"));
     }
 
     #[test]
@@ -161,7 +201,28 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 230_112);
+        let Some(input) = real_input(8) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(8, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("303\n2a5\n653\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "`a`");
+
+        let error = try_part_2("303\n25\n653\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a row of 3 trees");
+
+        let error = try_part_1("").unwrap_err();
+        assert_eq!(error.found, "end of input");
+        assert_eq!(error.expected, "a row of trees");
+    }
+
+    #[test]
+    fn trailing_newline_test() {
+        assert_eq!(part_1("303\n255\n653\n"), part_1("303\n255\n653"));
     }
 
     #[test]
@@ -173,4 +234,24 @@ mod tests {
             .collect();
         assert_eq!(taken, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day08>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day08>(&input);
+        }
+    }
 }
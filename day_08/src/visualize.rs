@@ -0,0 +1,89 @@
+use crate::{Day08, Grove};
+use common::{
+    visualize::{Color, Frame, Visualize},
+    ParseError,
+};
+use grid::Direction;
+
+impl Visualize for Day08 {
+    /// Draws the heights of the trees. For part 1, the trees that are visible from outside the
+    /// grove stand out; for part 2, the tree with the highest scenic score and the trees it can
+    /// see do.
+    fn frames(part: u8, input: &str) -> Result<Vec<Frame>, ParseError> {
+        let grove = Grove::try_from(input)?;
+        let trees = &grove.trees;
+        let mut frame = Frame::new(trees.num_rows(), trees.num_columns());
+        for point in trees.points() {
+            let color = if part == 1 && grove.is_visible(point) {
+                Color::Green
+            } else {
+                Color::Dim
+            };
+            frame.set(point.row, point.column, char::from(trees[point]), color);
+        }
+
+        if part == 1 {
+            let num_visible = grove.count_visible_trees();
+            frame.caption = format!("{num_visible} trees are visible from outside the grove");
+        } else {
+            let best = trees
+                .points()
+                .max_by_key(|&point| grove.scenic_score(point))
+                .unwrap();
+            for direction in Direction::ORTHOGONAL {
+                let num_seen = grove.count_in_line_of_sight(best, direction);
+                for point in trees.ray(best, direction).take(num_seen) {
+                    frame.set(
+                        point.row,
+                        point.column,
+                        char::from(trees[point]),
+                        Color::Yellow,
+                    );
+                }
+            }
+            frame.set(best.row, best.column, char::from(trees[best]), Color::Red);
+            frame.caption = format!(
+                "the tree in row {}, column {} has the highest scenic score: {}",
+                best.row + 1,
+                best.column + 1,
+                grove.scenic_score(best)
+            );
+        }
+        Ok(vec![frame])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::visualize::{Ascii, Renderer};
+
+    #[test]
+    fn test_frames() {
+        let input = include_str!("../sample-input.txt");
+        let frames = Day08::frames(1, input).unwrap();
+        assert_eq!(
+            Ascii.render(&frames[0]),
+            "\
+30373
+25512
+65332
+33549
+35390
+21 trees are visible from outside the grove
+"
+        );
+        assert_eq!(frames[0].get(1, 1).color, Color::Green);
+        assert_eq!(frames[0].get(1, 3).color, Color::Dim);
+
+        let frames = Day08::frames(2, input).unwrap();
+        assert_eq!(frames[0].get(3, 2).color, Color::Red);
+        assert_eq!(frames[0].get(0, 2).color, Color::Dim);
+        assert_eq!(frames[0].get(1, 2).color, Color::Yellow);
+        assert_eq!(frames[0].get(3, 0).color, Color::Yellow);
+        assert_eq!(
+            frames[0].caption,
+            "the tree in row 4, column 3 has the highest scenic score: 8"
+        );
+    }
+}
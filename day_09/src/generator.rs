@@ -0,0 +1,30 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+/// Generates about `size` bytes of head motions, each in a random direction and of 1 to 20 steps.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 8);
+    while input.len() < size {
+        let direction = rng.choose(&['U', 'R', 'D', 'L']);
+        writeln!(input, "{direction} {}", rng.range(1..=20)).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day09;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((10_000..10_008).contains(&input.len()));
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day09::try_part_1(&input).is_ok());
+        assert!(Day09::try_part_2(&input).is_ok());
+    }
+}
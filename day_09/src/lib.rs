@@ -1,78 +1,217 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod generator;
+#[cfg(feature = "visualize")]
+mod visualize;
+
+use common::{
+    stream::{LineReader, ReadError},
+    ParseError, Solution,
+};
 use std::{
     cmp::Ordering::{Equal, Greater, Less},
     collections::HashSet,
+    io::BufRead,
 };
+use Direction::{Down, Left, Right, Up};
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    let head_motions = parse(input);
-    simulate_rope::<2>(head_motions)
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    let head_motions = parse(input);
-    simulate_rope::<10>(head_motions)
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not a direction and a number of steps, like `R 4`.
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Day09::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a line is not a direction and a number of steps, like `R 4`.
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Day09::try_part_2(input)
+}
+
+/// The knots are tracked in 32-bit coordinates, which this many steps cannot leave.
+const MAX_TOTAL_STEPS: u32 = i32::MAX as u32;
+
+/// Solves part 1 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    simulate_rope_from_reader::<2>(reader)
 }
 
-fn parse(input: &str) -> impl Iterator<Item = (&str, u8)> {
-    input.lines().map(|line| {
-        let (direction, num_steps) = line.split_once(' ').unwrap();
-        (direction, num_steps.parse::<u8>().unwrap())
-    })
+/// Solves part 2 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    simulate_rope_from_reader::<10>(reader)
 }
 
-fn simulate_rope<'a, const L: usize>(head_motions: impl Iterator<Item = (&'a str, u8)>) -> usize {
-    let mut rope: [(i16, i16); L] = [(0, 0); L];
+fn simulate_rope_from_reader<const L: usize>(reader: impl BufRead) -> Result<usize, ReadError> {
+    let mut reader = LineReader::new(reader);
+    let mut total_steps = 0;
+    let mut error = None;
+    let motions = reader
+        .parse_lines(|line| parse_motion(line, line, &mut total_steps))
+        .map_while(|motion| {
+            motion
+                .map_err(|motion_error| error = Some(motion_error))
+                .ok()
+        });
+    let num_positions = simulate_rope::<L>(motions);
+    error.map_or(Ok(num_positions), Err)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Parsed<'a> = HeadMotions;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<HeadMotions, ParseError> {
+        let mut total_steps = 0;
+        let motions = input
+            .lines()
+            .map(|line| parse_motion(input, line, &mut total_steps))
+            .collect::<Result<_, _>>()?;
+        Ok(HeadMotions { motions })
+    }
+
+    fn solve_1(_input: &str, head_motions: &HeadMotions) -> Result<usize, ParseError> {
+        Ok(simulate_rope::<2>(head_motions.motions.iter().copied()))
+    }
+
+    fn solve_2(_input: &str, head_motions: &HeadMotions) -> Result<usize, ParseError> {
+        Ok(simulate_rope::<10>(head_motions.motions.iter().copied()))
+    }
+}
+
+/// The motions of the head of the rope: a direction and a number of steps each.
+pub struct HeadMotions {
+    motions: Vec<(Direction, u8)>,
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// Parses a motion and adds its steps to `total_steps`, which must stay within
+/// [`MAX_TOTAL_STEPS`].
+fn parse_motion(
+    input: &str,
+    line: &str,
+    total_steps: &mut u32,
+) -> Result<(Direction, u8), ParseError> {
+    let (direction, num_steps) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(input, &line[line.len()..], "a space"))?;
+    let direction = match direction {
+        "U" => Up,
+        "R" => Right,
+        "D" => Down,
+        "L" => Left,
+        _ => return Err(ParseError::at(input, direction, "`U`, `R`, `D` or `L`")),
+    };
+    let num_steps = num_steps
+        .parse::<u8>()
+        .map_err(|_| ParseError::at(input, num_steps, "a number of steps up to 255"))?;
+    *total_steps += u32::from(num_steps);
+    if *total_steps > MAX_TOTAL_STEPS {
+        let expected = format!("at most {MAX_TOTAL_STEPS} steps in total");
+        return Err(ParseError::at(input, line, expected));
+    }
+    Ok((direction, num_steps))
+}
+
+fn simulate_rope<const L: usize>(head_motions: impl IntoIterator<Item = (Direction, u8)>) -> usize {
+    let mut rope = Rope::<L>::new();
 
     let mut tail_history = HashSet::new();
-    tail_history.insert(rope[L - 1]);
+    tail_history.insert(rope.tail());
 
     for (direction, num_steps) in head_motions {
         for _ in 0..num_steps {
-            let head = &mut rope[0];
-            match direction {
-                "U" => head.1 += 1,
-                "R" => head.0 += 1,
-                "D" => head.1 -= 1,
-                "L" => head.0 -= 1,
-                _ => panic!(),
-            };
-
-            for i in 1..L {
-                let previous_knot = rope[i - 1];
-                let knot = &mut rope[i];
-                if previous_knot.0.abs_diff(knot.0) > 1 || previous_knot.1.abs_diff(knot.1) > 1 {
-                    match previous_knot.0.cmp(&knot.0) {
-                        Greater => knot.0 += 1,
-                        Equal => (),
-                        Less => knot.0 -= 1,
-                    }
-                    match previous_knot.1.cmp(&knot.1) {
-                        Greater => knot.1 += 1,
-                        Equal => (),
-                        Less => knot.1 -= 1,
-                    }
-                }
-            }
-
-            tail_history.insert(rope[L - 1]);
+            rope.step(direction);
+            tail_history.insert(rope.tail());
         }
     }
 
     tail_history.len()
 }
 
+/// A rope of `L` knots, from the head to the tail, which all start at the origin.
+struct Rope<const L: usize> {
+    knots: [(i32, i32); L],
+}
+
+impl<const L: usize> Rope<L> {
+    fn new() -> Self {
+        Self { knots: [(0, 0); L] }
+    }
+
+    fn tail(&self) -> (i32, i32) {
+        self.knots[L - 1]
+    }
+
+    /// Moves the head one step, and every other knot after the one before it.
+    fn step(&mut self, direction: Direction) {
+        let head = &mut self.knots[0];
+        match direction {
+            Up => head.1 += 1,
+            Right => head.0 += 1,
+            Down => head.1 -= 1,
+            Left => head.0 -= 1,
+        }
+        common::trace!("move", knot = 0_usize, x = head.0, y = head.1);
+        for i in 1..L {
+            let previous_knot = self.knots[i - 1];
+            let knot = &mut self.knots[i];
+            if previous_knot.0.abs_diff(knot.0) > 1 || previous_knot.1.abs_diff(knot.1) > 1 {
+                match previous_knot.0.cmp(&knot.0) {
+                    Greater => knot.0 += 1,
+                    Equal => (),
+                    Less => knot.0 -= 1,
+                }
+                match previous_knot.1.cmp(&knot.1) {
+                    Greater => knot.1 += 1,
+                    Equal => (),
+                    Less => knot.1 -= 1,
+                }
+                common::trace!("move", knot = i, x = knot.0, y = knot.1);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
     const LARGE_SAMPLE_INPUT: &str = include_str!("../large-sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -81,7 +220,8 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 6_376);
+        let Some(input) = real_input(9) else { return };
+        assert_eq!(Answer::from(part_1(&input)), expected_answer(9, 1, "input"));
     }
 
     #[test]
@@ -92,6 +232,97 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 2_607);
+        let Some(input) = real_input(9) else { return };
+        assert_eq!(Answer::from(part_2(&input)), expected_answer(9, 2, "input"));
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("R 4\nX 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "`X`");
+
+        let error = try_part_2("R 4\nU\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "end of line");
+
+        let error = try_part_1("R 4\nU 256\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "`256`");
+    }
+
+    #[test]
+    fn long_motions_test() {
+        assert_eq!(part_2(&"R 255\n".repeat(200)), 50_992);
+    }
+
+    #[test]
+    fn from_reader_test() {
+        for input in [
+            SAMPLE_INPUT,
+            LARGE_SAMPLE_INPUT,
+            &generator::generate(10_000, 1),
+        ] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), try_part_2(input));
+        }
+
+        let Err(ReadError::Parse(error)) = part_2_from_reader("R 4\nU\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "end of line");
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(LARGE_SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(LARGE_SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(LARGE_SAMPLE_INPUT));
+            assert_eq!(
+                part_1_from_reader(input.as_bytes()).unwrap(),
+                part_1(LARGE_SAMPLE_INPUT)
+            );
+            assert_eq!(
+                part_2_from_reader(input.as_bytes()).unwrap(),
+                part_2(LARGE_SAMPLE_INPUT)
+            );
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let (_, events) = trace::collect(|| part_1("R 2\nU 1\n"));
+        let events: Vec<_> = events.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            events,
+            [
+                "move knot=0 x=1 y=0",
+                "move knot=0 x=2 y=0",
+                "move knot=1 x=1 y=0",
+                "move knot=0 x=2 y=1",
+            ]
+        );
+
+        let (_, events) = trace::collect(|| part_2(LARGE_SAMPLE_INPUT));
+        let tail_moves = events
+            .iter()
+            .filter(|event| event.field("knot") == Some(&trace::Value::Unsigned(9)))
+            .count();
+        assert_eq!(tail_moves, 35);
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day09>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day09>(&input);
+        }
     }
 }
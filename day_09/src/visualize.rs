@@ -0,0 +1,133 @@
+use crate::{Day09, Direction, Rope};
+use common::{
+    visualize::{Color, Frame, Visualize},
+    ParseError, Solution,
+};
+use std::collections::HashSet;
+
+/// At most this many frames are drawn after the first, spread evenly over the motions, as a full
+/// input has thousands of them.
+const MAX_FRAMES: usize = 100;
+
+impl Visualize for Day09 {
+    /// Draws the rope and the positions its tail has visited, at the start and after motions.
+    fn frames(part: u8, input: &str) -> Result<Vec<Frame>, ParseError> {
+        let motions = Day09::parse(input)?.motions;
+        Ok(if part == 1 {
+            rope_frames::<2>(&motions)
+        } else {
+            rope_frames::<10>(&motions)
+        })
+    }
+}
+
+fn rope_frames<const L: usize>(motions: &[(Direction, u8)]) -> Vec<Frame> {
+    // The rope is simulated twice: first to find the area that every frame has to show.
+    let mut rope = Rope::<L>::new();
+    let (mut min, mut max) = ((0, 0), (0, 0));
+    for &(direction, num_steps) in motions {
+        for _ in 0..num_steps {
+            rope.step(direction);
+            for &(x, y) in &rope.knots {
+                min = (min.0.min(x), min.1.min(y));
+                max = (max.0.max(x), max.1.max(y));
+            }
+        }
+    }
+    let area = Area { min, max };
+
+    let mut rope = Rope::<L>::new();
+    let mut tail_history = HashSet::from([rope.tail()]);
+    let mut frames = vec![area.draw(&rope, &tail_history, String::from("start"))];
+    let stride = motions.len().div_ceil(MAX_FRAMES);
+    for (i, &(direction, num_steps)) in motions.iter().enumerate() {
+        for _ in 0..num_steps {
+            rope.step(direction);
+            tail_history.insert(rope.tail());
+        }
+        if (i + 1) % stride == 0 || i + 1 == motions.len() {
+            let caption = format!(
+                "after motion {} of {}, the tail has visited {} positions",
+                i + 1,
+                motions.len(),
+                tail_history.len()
+            );
+            frames.push(area.draw(&rope, &tail_history, caption));
+        }
+    }
+    frames
+}
+
+/// The area the rope moves in, with `y` pointing up like in the puzzle.
+struct Area {
+    min: (i32, i32),
+    max: (i32, i32),
+}
+
+impl Area {
+    fn cell(&self, (x, y): (i32, i32)) -> (usize, usize) {
+        let row = usize::try_from(self.max.1 - y).unwrap();
+        let column = usize::try_from(x - self.min.0).unwrap();
+        (row, column)
+    }
+
+    fn draw<const L: usize>(
+        &self,
+        rope: &Rope<L>,
+        tail_history: &HashSet<(i32, i32)>,
+        caption: String,
+    ) -> Frame {
+        let (num_rows, num_columns) = self.cell((self.max.0, self.min.1));
+        let mut frame = Frame::new(num_rows + 1, num_columns + 1).with_caption(caption);
+        for &position in tail_history {
+            let (row, column) = self.cell(position);
+            frame.set(row, column, '#', Color::Dim);
+        }
+        let (row, column) = self.cell((0, 0));
+        frame.set(row, column, 's', Color::Dim);
+        // From the tail to the head, so that knots in front are drawn over those behind them.
+        for (i, &knot) in rope.knots.iter().enumerate().rev() {
+            let (row, column) = self.cell(knot);
+            let (glyph, color) = match i {
+                0 => ('H', Color::Red),
+                _ if L == 2 => ('T', Color::Yellow),
+                _ => (
+                    char::from_digit(u32::try_from(i).unwrap(), 10).unwrap(),
+                    Color::Yellow,
+                ),
+            };
+            frame.set(row, column, glyph, color);
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::visualize::{Ascii, Renderer};
+
+    #[test]
+    fn test_frames() {
+        let input = include_str!("../sample-input.txt");
+        let frames = Day09::frames(1, input).unwrap();
+        assert_eq!(frames.len(), 9);
+        assert_eq!(Ascii.render(&frames[0]), "\n\n\n\nH\nstart\n");
+        assert_eq!(
+            Ascii.render(&frames[8]),
+            concat!(
+                "  ##\n",
+                "   ##\n",
+                " TH##\n",
+                "    #\n",
+                "s###\n",
+                "after motion 8 of 8, the tail has visited 13 positions\n",
+            )
+        );
+
+        let long_input = "R 1\n".repeat(250);
+        let frames = Day09::frames(2, &long_input).unwrap();
+        assert_eq!(frames.len(), 85);
+        assert_eq!(frames[84].get(0, 241).glyph, '9');
+    }
+}
@@ -0,0 +1,37 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+/// Generates a program of about `size` bytes of `noop` and `addx` instructions, which keep the
+/// register between -40 and 80.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 10);
+    let mut x_register: i32 = 1;
+    while input.len() < size {
+        if rng.percent(30) {
+            input.push_str("noop\n");
+        } else {
+            let value = rng.range((-40 - x_register).max(-20)..=(80 - x_register).min(20));
+            x_register += value;
+            writeln!(input, "addx {value}").unwrap();
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day10;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((10_000..10_010).contains(&input.len()));
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day10::try_part_1(&input).is_ok());
+        assert_eq!(Day10::try_part_2(&input).unwrap().lines().count(), 6);
+    }
+}
@@ -1,64 +1,169 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
+pub mod generator;
+#[cfg(feature = "visualize")]
+mod visualize;
+
+use common::{
+    stream::{LineReader, ReadError},
+    ParseError, Solution,
+};
 use std::{
     fmt::{Display, Formatter, Result as FormatResult},
+    io::BufRead,
     iter::{once, repeat},
-    str::FromStr,
 };
 use CpuState::{Adding, Done, Ready};
 use Instruction::{AddX, Noop};
 
 #[must_use]
-pub fn part_1(input: &str) -> i32 {
+pub fn part_1(input: &str) -> i64 {
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+#[must_use]
+pub fn part_2(input: &str) -> String {
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a line is neither `noop` nor `addx` followed by a number.
+pub fn try_part_1(input: &str) -> Result<i64, ParseError> {
+    Day10::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a line is neither `noop` nor `addx` followed by a number.
+pub fn try_part_2(input: &str) -> Result<String, ParseError> {
+    Day10::try_part_2(input)
+}
+
+/// Solves part 1 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_1_from_reader(reader: impl BufRead) -> Result<i64, ReadError> {
+    run_program_from_reader(reader, sum_signal_strengths)
+}
+
+/// Solves part 2 while reading the input, keeping one line of it in memory at a time.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or does not match the puzzle.
+pub fn part_2_from_reader(reader: impl BufRead) -> Result<String, ReadError> {
+    run_program_from_reader(reader, draw_screen)
+}
+
+/// Runs `run` on the instructions as they are read. The lines it does not need are still read,
+/// so that the whole input is checked like it is by the other entry points.
+fn run_program_from_reader<T>(
+    reader: impl BufRead,
+    run: fn(&mut dyn Iterator<Item = Instruction>) -> T,
+) -> Result<T, ReadError> {
+    let mut reader = LineReader::new(reader);
+    let mut error = None;
+    let mut instructions = reader
+        .parse_lines(|line| Instruction::parse(line, line))
+        .map_while(|instruction| {
+            instruction
+                .map_err(|instruction_error| error = Some(instruction_error))
+                .ok()
+        });
+    let result = run(&mut instructions);
+    instructions.for_each(drop);
+    error.map_or(Ok(result), Err)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed<'a> = Program;
+    type Answer1 = i64;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Program, ParseError> {
+        let instructions = input
+            .lines()
+            .map(|line| Instruction::parse(input, line))
+            .collect::<Result<_, _>>()?;
+        Ok(Program { instructions })
+    }
+
+    fn solve_1(_input: &str, program: &Program) -> Result<i64, ParseError> {
+        Ok(sum_signal_strengths(
+            &mut program.instructions.iter().copied(),
+        ))
+    }
+
+    fn solve_2(_input: &str, program: &Program) -> Result<String, ParseError> {
+        Ok(draw_screen(&mut program.instructions.iter().copied()))
+    }
+}
+
+fn sum_signal_strengths(instructions: &mut dyn Iterator<Item = Instruction>) -> i64 {
     let mut result = 0;
 
-    let instructions = input
-        .lines()
-        .map(|line| Instruction::from_str(line).unwrap());
     let mut cpu = Cpu::new(instructions);
 
     for steps in once(19).chain(repeat(40)).take(6) {
         for _ in 0..steps {
             cpu.tick();
         }
-        let signal_strenth = (cpu.cycles + 1) * cpu.x_register;
+        let signal_strenth = i64::from(cpu.cycles + 1) * cpu.x_register;
+        common::trace!(
+            "signal",
+            cycle = cpu.cycles + 1,
+            x = cpu.x_register,
+            strength = signal_strenth
+        );
         result += signal_strenth;
     }
 
     result
 }
 
-#[must_use]
-pub fn part_2(input: &str) -> String {
-    let instructions = input
-        .lines()
-        .map(|line| Instruction::from_str(line).unwrap());
+fn draw_screen(instructions: &mut dyn Iterator<Item = Instruction>) -> String {
     let mut cpu = Cpu::new(instructions);
     let mut crt = Crt::new();
 
-    while !cpu.is_done() {
+    // The screen is drawn once, even if the program runs for longer.
+    while !cpu.is_done() && cpu.cycles < CRT_PIXELS {
         crt.draw_pixel(cpu.cycles, cpu.x_register);
         cpu.tick();
     }
 
-    format!("{}", crt)
+    crt.to_string()
 }
 
-#[derive(Debug)]
+/// The instructions for the CPU of the handheld device.
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, Copy)]
 enum Instruction {
     Noop,
     AddX(i32),
 }
 
-impl FromStr for Instruction {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "noop" {
+impl Instruction {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        if line == "noop" {
             Ok(Noop)
+        } else if let Some(value) = line.strip_prefix("addx ") {
+            value
+                .parse()
+                .map(AddX)
+                .map_err(|_| ParseError::at(input, value, "a 32-bit integer"))
         } else {
-            Ok(AddX(s.strip_prefix("addx ").unwrap().parse().unwrap()))
+            let instruction = line.split(' ').next().unwrap();
+            Err(ParseError::at(input, instruction, "`noop` or `addx`"))
         }
     }
 }
@@ -70,7 +175,8 @@ where
 {
     instructions: I,
     cycles: i32,
-    x_register: i32,
+    /// Wide enough that adding 32-bit values to it for every cycle on the screen cannot overflow.
+    x_register: i64,
     state: CpuState,
 }
 
@@ -87,7 +193,9 @@ where
         }
     }
 
+    /// Runs one cycle, during which the X register still holds the value from before it.
     fn tick(&mut self) {
+        common::trace!("tick", cycle = self.cycles + 1, x = self.x_register);
         match self.state {
             Ready => {
                 let instruction = self.instructions.next();
@@ -98,7 +206,7 @@ where
                 };
             }
             Adding(value) => {
-                self.x_register += value;
+                self.x_register += i64::from(value);
                 self.state = Ready;
             }
             Done => {}
@@ -118,16 +226,18 @@ enum CpuState {
     Done,
 }
 
-struct Crt([u8; 240]);
+const CRT_PIXELS: i32 = 240;
+
+struct Crt([u8; CRT_PIXELS as usize]);
 
 impl Crt {
     fn new() -> Self {
-        Self([b'.'; 240])
+        Self([b'.'; CRT_PIXELS as usize])
     }
 
-    fn draw_pixel(&mut self, cycle: i32, x_register: i32) {
-        let adjusted_register = x_register + 40 * (cycle / 40);
-        if (cycle - 1..=cycle + 1).contains(&adjusted_register) {
+    fn draw_pixel(&mut self, cycle: i32, x_register: i64) {
+        let adjusted_register = x_register + i64::from(40 * (cycle / 40));
+        if (i64::from(cycle) - 1..=i64::from(cycle) + 1).contains(&adjusted_register) {
             self.0[usize::try_from(cycle).unwrap()] = b'#';
         }
     }
@@ -145,9 +255,11 @@ impl Display for Crt {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
     const SAMLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -156,7 +268,11 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 14_160);
+        let Some(input) = real_input(10) else { return };
+        assert_eq!(
+            Answer::from(part_1(&input)),
+            expected_answer(10, 1, "input")
+        );
     }
 
     #[test]
@@ -177,17 +293,103 @@ mod tests {
 
     #[test]
     fn part_2_test() {
+        let Some(input) = real_input(10) else { return };
         assert_eq!(
-            part_2(INPUT),
-            String::from(
-                "###....##.####.###..###..####.####..##..
-#..#....#.#....#..#.#..#.#....#....#..#.
-#..#....#.###..#..#.#..#.###..###..#....
-###.....#.#....###..###..#....#....#....
-#.#..#..#.#....#.#..#....#....#....#..#.
-#..#..##..####.#..#.#....####.#.....##..
-"
-            )
+            Answer::from(part_2(&input)),
+            expected_answer(10, 2, "input")
+        );
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = try_part_1("noop\naddx 3\naddx\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.found, "`addx`");
+        assert_eq!(error.expected, "`noop` or `addx`");
+
+        let error = try_part_2("noop\naddx -5x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.found, "`-5x`");
+
+        let error = try_part_1("nop\n").unwrap_err();
+        assert_eq!(error.found, "`nop`");
+    }
+
+    #[test]
+    fn from_reader_test() {
+        for input in [SAMLE_INPUT, &generator::generate(10_000, 1)] {
+            let part_1 = part_1_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_1), try_part_1(input));
+            let part_2 = part_2_from_reader(input.as_bytes()).unwrap();
+            assert_eq!(Ok(part_2), try_part_2(input));
+        }
+
+        let input = format!("{}nop\n", "noop\n".repeat(300));
+        let Err(ReadError::Parse(error)) = part_2_from_reader(input.as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column), (301, 1));
+        assert_eq!(error.found, "`nop`");
+    }
+
+    #[test]
+    fn large_values_test() {
+        let input = "addx 2000000000\n".repeat(200);
+        assert_eq!(part_1(&input), 112_960_000_000_720);
+        assert_eq!(part_2(&input).lines().count(), 6);
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMLE_INPUT));
+            assert_eq!(
+                part_1_from_reader(input.as_bytes()).unwrap(),
+                part_1(SAMLE_INPUT)
+            );
+            assert_eq!(
+                part_2_from_reader(input.as_bytes()).unwrap(),
+                part_2(SAMLE_INPUT)
+            );
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let (_, events) = trace::collect(|| part_2("noop\naddx 3\naddx -5\n"));
+        let events: Vec<_> = events.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            events,
+            [
+                "tick cycle=1 x=1",
+                "tick cycle=2 x=1",
+                "tick cycle=3 x=1",
+                "tick cycle=4 x=4",
+                "tick cycle=5 x=4",
+                "tick cycle=6 x=-1",
+            ]
         );
+
+        let (_, events) = trace::collect(|| part_1(SAMLE_INPUT));
+        let signals: Vec<_> = events
+            .iter()
+            .filter(|event| event.name == "signal")
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(signals[0], "signal cycle=20 x=21 strength=420");
+        assert_eq!(signals[5], "signal cycle=220 x=18 strength=3960");
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day10>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day10>(&input);
+        }
     }
 }
@@ -0,0 +1,64 @@
+use crate::{Cpu, Crt, Day10, CRT_PIXELS};
+use common::{
+    visualize::{Color, Frame, Visualize},
+    ParseError, Solution,
+};
+
+impl Visualize for Day10 {
+    /// Draws the screen after every cycle, with the pixel that was just drawn highlighted and the
+    /// sprite below it. Both parts run the same program, so they have the same frames.
+    fn frames(_part: u8, input: &str) -> Result<Vec<Frame>, ParseError> {
+        let program = Day10::parse(input)?;
+        let mut cpu = Cpu::new(program.instructions.iter().copied());
+        let mut crt = Crt::new();
+        let mut frames = Vec::new();
+        while !cpu.is_done() && cpu.cycles < CRT_PIXELS {
+            crt.draw_pixel(cpu.cycles, cpu.x_register);
+            frames.push(draw(&crt, cpu.cycles, cpu.x_register));
+            cpu.tick();
+        }
+        Ok(frames)
+    }
+}
+
+fn draw(crt: &Crt, cycle: i32, x_register: i64) -> Frame {
+    let cycle = usize::try_from(cycle).unwrap();
+    let caption = format!("during cycle {}, X is {x_register}", cycle + 1);
+    let mut frame = Frame::new(7, 40).with_caption(caption);
+    for (i, &pixel) in crt.0.iter().enumerate().take(cycle + 1) {
+        let color = match (i == cycle, pixel) {
+            (true, _) => Color::Red,
+            (false, b'#') => Color::Default,
+            (false, _) => Color::Dim,
+        };
+        frame.set(i / 40, i % 40, char::from(pixel), color);
+    }
+    for column in (x_register - 1..=x_register + 1).filter_map(|x| usize::try_from(x).ok()) {
+        if column < 40 {
+            frame.set(6, column, '=', Color::Cyan);
+        }
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::visualize::{Ascii, Renderer};
+
+    #[test]
+    fn test_frames() {
+        let input = include_str!("../sample-input.txt");
+        let frames = Day10::frames(2, input).unwrap();
+        assert_eq!(frames.len(), 240);
+        assert_eq!(
+            Ascii.render(&frames[3]),
+            "##..\n\n\n\n\n\n               ===\nduring cycle 4, X is 16\n"
+        );
+        assert_eq!(frames[3].get(0, 3).color, Color::Red);
+        assert_eq!(
+            Ascii.render(&frames[239]).lines().nth(5),
+            Some("#######.......#######.......#######.....")
+        );
+    }
+}
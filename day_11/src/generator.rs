@@ -0,0 +1,65 @@
+use common::rng::Rng;
+use std::fmt::Write;
+
+/// The test divisors. Their product must stay small enough for squaring a worry level below it
+/// to fit in 64 bits, which is why the number of monkeys does not grow with the input.
+const DIVISORS: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+/// Generates the notes on eight monkeys, holding about `size` bytes of starting items between
+/// them. Each monkey throws to two other monkeys, chosen at random.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let num_monkeys = DIVISORS.len();
+    let mut divisors = DIVISORS;
+    rng.shuffle(&mut divisors);
+    let items_size = size.saturating_sub(num_monkeys * 160) / num_monkeys;
+
+    let mut input = String::with_capacity(size + 1_000);
+    for (index, divisor) in divisors.into_iter().enumerate() {
+        if index > 0 {
+            input.push('\n');
+        }
+        let mut items = rng.range(50..=99).to_string();
+        while items.len() < items_size {
+            write!(items, ", {}", rng.range(50..=99)).unwrap();
+        }
+        let operation = match rng.range(0..=4) {
+            0 => String::from("old * old"),
+            1 | 2 => format!("old + {}", rng.range(1..=8)),
+            _ => format!("old * {}", rng.range(2..=19)),
+        };
+        let mut others: Vec<_> = (0..num_monkeys).filter(|&other| other != index).collect();
+        rng.shuffle(&mut others);
+        write!(
+            input,
+            "\
+Monkey {index}:
+  Starting items: {items}
+  Operation: new = {operation}
+  Test: divisible by {divisor}
+    If true: throw to monkey {}
+    If false: throw to monkey {}
+",
+            others[0], others[1]
+        )
+        .unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day11;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((9_000..11_000).contains(&input.len()), "{}", input.len());
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day11::try_part_1(&input).is_ok());
+        assert!(Day11::try_part_2(&generate(0, 2)).is_ok());
+    }
+}
@@ -1,48 +1,138 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
-use std::{collections::VecDeque, str::FromStr, usize};
+pub mod generator;
+
+use common::{ParseError, Solution};
+use std::collections::VecDeque;
 
 #[must_use]
 pub fn part_1(input: &str) -> u64 {
-    let mut monkeys: Vec<Monkey> = input.split("\n\n").map(|i| i.parse().unwrap()).collect();
-    simulate_rounds(&mut monkeys, 20, 3);
-    level_of_monkey_business(&monkeys)
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u64 {
-    let mut monkeys: Vec<Monkey> = input.split("\n\n").map(|i| i.parse().unwrap()).collect();
-    simulate_rounds(&mut monkeys, 10_000, 1);
-    level_of_monkey_business(&monkeys)
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if one of the monkeys' notes cannot be parsed.
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    Day11::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if one of the monkeys' notes cannot be parsed.
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    Day11::try_part_2(input)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed<'a> = Monkeys<'a>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Monkeys<'_>, ParseError> {
+        let num_monkeys = input.split("\n\n").count();
+        let mut common_divisor = 1;
+        let monkeys = input
+            .split("\n\n")
+            .enumerate()
+            .map(|(index, notes)| {
+                Monkey::parse(input, notes, index, num_monkeys, &mut common_divisor)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Monkeys {
+            monkeys,
+            common_divisor,
+        })
+    }
+
+    fn solve_1(input: &str, monkeys: &Monkeys<'_>) -> Result<u64, ParseError> {
+        let mut monkeys = monkeys.clone();
+        monkeys.simulate_rounds(input, 20, 3)?;
+        monkeys.level_of_monkey_business(input)
+    }
+
+    fn solve_2(input: &str, monkeys: &Monkeys<'_>) -> Result<u64, ParseError> {
+        let mut monkeys = monkeys.clone();
+        monkeys.simulate_rounds(input, 10_000, 1)?;
+        monkeys.level_of_monkey_business(input)
+    }
 }
 
-fn simulate_rounds(monkeys: &mut Vec<Monkey>, num_rounds: usize, worry_divisor: u64) {
-    let common_divisor = monkeys.iter().map(|monkey| monkey.test_divisor).product();
-
-    for _ in 0..num_rounds {
-        for i in 0..monkeys.len() {
-            for _ in 0..monkeys[i].items.len() {
-                let monkey = &mut monkeys[i];
-                monkey.inspect_item();
-                monkey.reduce_worry_level(worry_divisor, common_divisor);
-                let (item, to) = monkey.throw_item();
-                monkeys[to].items.push_back(item);
+/// The monkeys as described by their notes, before they have inspected any items.
+#[derive(Clone)]
+pub struct Monkeys<'a> {
+    monkeys: Vec<Monkey<'a>>,
+    /// The product of the test divisors. Worry levels are kept below it, which does not change
+    /// the outcome of any test.
+    common_divisor: u64,
+}
+
+impl Monkeys<'_> {
+    fn simulate_rounds(
+        &mut self,
+        input: &str,
+        num_rounds: usize,
+        worry_divisor: u64,
+    ) -> Result<(), ParseError> {
+        let monkeys = &mut self.monkeys;
+        for round in 1..=num_rounds {
+            for i in 0..monkeys.len() {
+                for _ in 0..monkeys[i].items.len() {
+                    let monkey = &mut monkeys[i];
+                    common::trace!(
+                        "inspect",
+                        round = round,
+                        monkey = i,
+                        worry_level = monkey.items[0]
+                    );
+                    monkey.inspect_item(input, worry_divisor, self.common_divisor)?;
+                    let (item, to) = monkey.throw_item();
+                    common::trace!("throw", monkey = i, worry_level = item, to = to);
+                    monkeys[to].items.push_back(item);
+                }
             }
         }
+        Ok(())
     }
-}
 
-fn level_of_monkey_business(monkeys: &[Monkey]) -> u64 {
-    let mut inspection_counts: Vec<u64> = monkeys.iter().map(|monkey| monkey.inspections).collect();
-    inspection_counts.sort_unstable();
-    inspection_counts.iter().rev().take(2).product()
+    fn level_of_monkey_business(&self, input: &str) -> Result<u64, ParseError> {
+        let mut inspection_counts: Vec<u64> = self
+            .monkeys
+            .iter()
+            .map(|monkey| monkey.inspections)
+            .collect();
+        inspection_counts.sort_unstable();
+        inspection_counts
+            .iter()
+            .rev()
+            .take(2)
+            .try_fold(1_u64, |product, &count| product.checked_mul(count))
+            .ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    &input[input.len()..],
+                    "a level of monkey business that fits in 64 bits",
+                )
+            })
+    }
 }
 
-struct Monkey {
+#[derive(Clone)]
+struct Monkey<'a> {
     items: VecDeque<u64>,
+    /// The operation as written in the notes, to point at in errors.
+    operation: &'a str,
     operation_left_operand: Option<u64>,
-    operator: fn(u64, u64) -> u64,
+    operator: fn(u128, u128) -> Option<u128>,
     operation_right_operand: Option<u64>,
     test_divisor: u64,
     test_true_throw: usize,
@@ -50,24 +140,37 @@ struct Monkey {
     inspections: u64,
 }
 
-impl Monkey {
-    fn inspect_item(&mut self) {
+impl Monkey<'_> {
+    /// Applies the operation to the first item, and then reduces the new worry level. The
+    /// operation is carried out in 128 bits, where adding or multiplying two worry levels cannot
+    /// overflow, but subtracting can still take the worry level below 0.
+    fn inspect_item(
+        &mut self,
+        input: &str,
+        divisor: u64,
+        common_divisor: u64,
+    ) -> Result<(), ParseError> {
         let item = self.items[0];
-        self.items[0] = (self.operator)(
-            self.operation_left_operand.unwrap_or(item),
-            self.operation_right_operand.unwrap_or(item),
-        );
+        let worry_level = (self.operator)(
+            u128::from(self.operation_left_operand.unwrap_or(item)),
+            u128::from(self.operation_right_operand.unwrap_or(item)),
+        )
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                self.operation,
+                "an operation that keeps worry levels from going below 0",
+            )
+        })?;
+        let worry_level = worry_level / u128::from(divisor) % u128::from(common_divisor);
+        self.items[0] = u64::try_from(worry_level).unwrap();
         self.inspections += 1;
-    }
-
-    fn reduce_worry_level(&mut self, divisor: u64, common_divisor: u64) {
-        self.items[0] /= divisor;
-        self.items[0] %= common_divisor;
+        Ok(())
     }
 
     fn throw_item(&mut self) -> (u64, usize) {
         let item = self.items.pop_front().unwrap();
-        if item % self.test_divisor == 0 {
+        if item.is_multiple_of(self.test_divisor) {
             (item, self.test_true_throw)
         } else {
             (item, self.test_false_throw)
@@ -75,57 +178,90 @@ impl Monkey {
     }
 }
 
-impl FromStr for Monkey {
-    type Err = ();
+impl<'a> Monkey<'a> {
+    /// Parses the notes on the monkey with the given index, and multiplies `common_divisor` by
+    /// its test divisor.
+    fn parse(
+        input: &str,
+        notes: &'a str,
+        index: usize,
+        num_monkeys: usize,
+        common_divisor: &mut u64,
+    ) -> Result<Self, ParseError> {
+        let mut lines = notes.lines();
+        let mut next_line = |prefix: &str| {
+            let line = lines.next().unwrap_or(&notes[notes.len()..]);
+            line.strip_prefix(prefix)
+                .ok_or_else(|| ParseError::at(input, line, format!("`{}`", prefix.trim())))
+        };
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut lines = input.lines().skip(1);
-        let items: VecDeque<u64> = lines
-            .next()
-            .unwrap()
-            .strip_prefix("  Starting items: ")
-            .unwrap()
+        let header = next_line("Monkey ")?;
+        if header != format!("{index}:") {
+            return Err(ParseError::at(input, header, format!("`{index}:`")));
+        }
+        let items = next_line("  Starting items: ")?
             .split(", ")
-            .map(|item| item.parse().unwrap())
-            .collect();
-        let mut operation_parts = lines
-            .next()
-            .unwrap()
-            .strip_prefix("  Operation: new = ")
-            .unwrap()
-            .split(' ');
-        let operation_left_operand = operation_parts.next().unwrap().parse::<u64>().ok();
-        let operator = match operation_parts.next().unwrap() {
-            "+" => std::ops::Add::add,
-            "-" => std::ops::Sub::sub,
-            "*" => std::ops::Mul::mul,
-            _ => panic!(),
+            .map(|item| {
+                item.parse()
+                    .map_err(|_| ParseError::at(input, item, "a worry level"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let operation = next_line("  Operation: new = ")?;
+        let mut operation_parts = operation.split(' ');
+        let mut next_part = || {
+            operation_parts
+                .next()
+                .unwrap_or(&operation[operation.len()..])
         };
-        let operation_right_operand = operation_parts.next().unwrap().parse::<u64>().ok();
-        let test_divisor = lines
-            .next()
-            .unwrap()
-            .strip_prefix("  Test: divisible by ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let test_true_throw = lines
-            .next()
-            .unwrap()
-            .strip_prefix("    If true: throw to monkey ")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let test_false_throw = lines
-            .next()
-            .unwrap()
-            .strip_prefix("    If false: throw to monkey ")
-            .unwrap()
-            .parse()
-            .unwrap();
+        let operation_left_operand = parse_operand(input, next_part())?;
+        let operator = match next_part() {
+            "+" => u128::checked_add,
+            "-" => u128::checked_sub,
+            "*" => u128::checked_mul,
+            operator => return Err(ParseError::at(input, operator, "`+`, `-` or `*`")),
+        };
+        let operation_right_operand = parse_operand(input, next_part())?;
+        let rest = next_part();
+        if !rest.is_empty() {
+            return Err(ParseError::at(input, rest, "end of line"));
+        }
+
+        let test_divisor_text = next_line("  Test: divisible by ")?;
+        let test_divisor = match test_divisor_text.parse() {
+            Ok(divisor) if divisor > 0 => divisor,
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    test_divisor_text,
+                    "a divisor greater than 0",
+                ))
+            }
+        };
+        *common_divisor = common_divisor.checked_mul(test_divisor).ok_or_else(|| {
+            ParseError::at(
+                input,
+                test_divisor_text,
+                "a divisor that keeps the product of all divisors within 64 bits",
+            )
+        })?;
+        let test_true_throw = parse_monkey_index(
+            input,
+            next_line("    If true: throw to monkey ")?,
+            num_monkeys,
+        )?;
+        let test_false_throw = parse_monkey_index(
+            input,
+            next_line("    If false: throw to monkey ")?,
+            num_monkeys,
+        )?;
+        if let Some(line) = lines.find(|line| !line.is_empty()) {
+            return Err(ParseError::at(input, line, "a blank line"));
+        }
 
         Ok(Self {
             items,
+            operation,
             operation_left_operand,
             operator,
             operation_right_operand,
@@ -137,12 +273,37 @@ impl FromStr for Monkey {
     }
 }
 
+/// Parses one side of an operation, which is either a number or `old` (returned as `None`).
+fn parse_operand(input: &str, operand: &str) -> Result<Option<u64>, ParseError> {
+    if operand == "old" {
+        Ok(None)
+    } else {
+        operand
+            .parse()
+            .map(Some)
+            .map_err(|_| ParseError::at(input, operand, "`old` or a number"))
+    }
+}
+
+fn parse_monkey_index(input: &str, index: &str, num_monkeys: usize) -> Result<usize, ParseError> {
+    match index.parse() {
+        Ok(index) if index < num_monkeys => Ok(index),
+        _ => Err(ParseError::at(
+            input,
+            index,
+            format!("a monkey from 0 to {}", num_monkeys - 1),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
-    const INPUT: &str = include_str!("../input.txt");
 
     #[test]
     fn part_1_sample_test() {
@@ -151,7 +312,11 @@ mod tests {
 
     #[test]
     fn part_1_test() {
-        assert_eq!(part_1(INPUT), 100_345);
+        let Some(input) = real_input(11) else { return };
+        assert_eq!(
+            Answer::from(part_1(&input)),
+            expected_answer(11, 1, "input")
+        );
     }
 
     #[test]
@@ -161,6 +326,124 @@ mod tests {
 
     #[test]
     fn part_2_test() {
-        assert_eq!(part_2(INPUT), 28_537_348_205);
+        let Some(input) = real_input(11) else { return };
+        assert_eq!(
+            Answer::from(part_2(&input)),
+            expected_answer(11, 2, "input")
+        );
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let monkey = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 0";
+
+        let input = format!(
+            "{monkey}\n\n{}",
+            monkey
+                .replace("Monkey 0", "Monkey 1")
+                .replace("monkey 1", "monkey 2")
+        );
+        let error = try_part_1(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (12, 30));
+        assert_eq!(error.expected, "a monkey from 0 to 1");
+
+        let error = try_part_2(&monkey.replace("98", "9x")).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 23));
+        assert_eq!(error.found, "`9x`");
+
+        let error = try_part_1(&monkey.replace("* 19", "/ 19")).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 24));
+        assert_eq!(error.expected, "`+`, `-` or `*`");
+
+        let error = try_part_2(&monkey.replace("old * 19", "old *")).unwrap_err();
+        assert_eq!(error.found, "end of line");
+
+        let error = try_part_1(&monkey.replace("by 23", "by 0")).unwrap_err();
+        assert_eq!(error.expected, "a divisor greater than 0");
+
+        let error = try_part_2(&monkey.replace("Test", "Tset")).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.expected, "`Test: divisible by`");
+
+        let error = try_part_1(&monkey.replace("Monkey 0", "Monkey 3")).unwrap_err();
+        assert_eq!(error.expected, "`0:`");
+    }
+
+    #[test]
+    fn arithmetic_errors_test() {
+        let monkey = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old - 100
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+        let error = try_part_1(monkey).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 20));
+        assert_eq!(
+            error.expected,
+            "an operation that keeps worry levels from going below 0"
+        );
+
+        let input = format!(
+            "{monkey}\n\n{}",
+            monkey
+                .replace("Monkey 0", "Monkey 1")
+                .replace("by 23", "by 18446744073709551557")
+        );
+        let error = try_part_2(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (11, 22));
+        assert_eq!(
+            error.expected,
+            "a divisor that keeps the product of all divisors within 64 bits"
+        );
+
+        let monkey = monkey.replace("old - 100", "old * 18446744073709551557");
+        assert!(try_part_2(&monkey).is_ok());
+    }
+
+    #[test]
+    fn whitespace_test() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let (_, events) = trace::collect(|| part_1(SAMPLE_INPUT));
+        let first_events: Vec<_> = events[..4].iter().map(ToString::to_string).collect();
+        assert_eq!(
+            first_events,
+            [
+                "inspect round=1 monkey=0 worry_level=79",
+                "throw monkey=0 worry_level=500 to=3",
+                "inspect round=1 monkey=0 worry_level=98",
+                "throw monkey=0 worry_level=620 to=3",
+            ]
+        );
+        let inspections = events
+            .iter()
+            .filter(|event| event.name == "inspect")
+            .count();
+        assert_eq!(inspections, 101 + 95 + 7 + 105);
+    }
+
+    proptest! {
+        #[test]
+        fn mutated_input_test(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day11>(&input);
+        }
+
+        #[test]
+        fn random_input_test(input in ".*") {
+            solve_both::<Day11>(&input);
+        }
     }
 }
@@ -0,0 +1,46 @@
+use common::rng::Rng;
+use grid::{GridBuf, Point};
+
+/// Generates a square height map of about `size` bytes, and at least 14 by 14 so that there is
+/// room to climb from `a` to `z`. `S` is in the top left and `E` in the bottom right corner, and
+/// a random path of right and down steps between them climbs at most one level per step, so
+/// there is always a route. The other heights are random.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let width = size.isqrt().max(14);
+    let heights = (0..width * width).map(|_| rng.range(b'a'..=b'z')).collect();
+    let mut map = GridBuf::from_vec(width, heights);
+
+    let mut steps: Vec<_> = [(0, 1), (1, 0)]
+        .into_iter()
+        .flat_map(|step| [step].repeat(width - 1))
+        .collect();
+    rng.shuffle(&mut steps);
+    let mut point = Point::new(0, 0);
+    for (i, (row_step, column_step)) in steps.iter().enumerate() {
+        map[point] = b'a' + u8::try_from(25 * i / steps.len()).unwrap();
+        point = Point::new(point.row + row_step, point.column + column_step);
+    }
+    map[Point::new(0, 0)] = b'S';
+    map[point] = b'E';
+
+    map.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day12;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert_eq!(input.len(), 100 * 101);
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day12::try_part_1(&input).unwrap() <= 198);
+        assert!(Day12::try_part_2(&input).is_ok());
+        assert_eq!(Day12::try_part_1(&generate(0, 2)), Ok(26));
+    }
+}
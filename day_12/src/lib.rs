@@ -1,155 +1,184 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
-use std::{
-    collections::VecDeque,
-    ops::{Index, IndexMut},
-};
+pub mod generator;
+#[cfg(feature = "visualize")]
+mod visualize;
+
+use common::{ParseError, Solution};
+use grid::{GridBuf, GridRef, Point};
+use std::{collections::VecDeque, ops::Index};
 
 #[must_use]
-pub fn part_1(input: &[u8]) -> u32 {
-    let map = HeightMap::from(input);
-    let start = map.find(b'S');
-    let end = map.find(b'E');
-    find_shortest_route(&map, start, end).unwrap()
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
-pub fn part_2(input: &[u8]) -> u32 {
-    let map = HeightMap::from(input);
-    let end = map.find(b'E');
-    map.find_lowest_points()
-        .filter_map(|start| find_shortest_route(&map, start, end))
-        .min()
-        .unwrap()
+pub fn part_2(input: &str) -> u32 {
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if the input is not a rectangular map of heights with one start and one end
+/// position, or if there is no route from the start to the end.
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Day12::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if the input is not a rectangular map of heights with one start and one end
+/// position, or if there is no route from any lowest point to the end.
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Day12::try_part_2(input)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed<'a> = HeightMap<'a>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<HeightMap<'_>, ParseError> {
+        HeightMap::try_from(input)
+    }
+
+    fn solve_1(_input: &str, map: &HeightMap<'_>) -> Result<u32, ParseError> {
+        find_shortest_route(map, map.start, map.end)
+            .ok_or_else(|| map.error_at(map.start, "a route from `S` to `E`"))
+    }
+
+    fn solve_2(_input: &str, map: &HeightMap<'_>) -> Result<u32, ParseError> {
+        map.find_lowest_points()
+            .filter_map(|start| find_shortest_route(map, start, map.end))
+            .min()
+            .ok_or_else(|| map.error_at(map.end, "a route from a lowest point to `E`"))
+    }
 }
 
 fn find_shortest_route(map: &HeightMap, start: Point, end: Point) -> Option<u32> {
-    let mut cache = Cache::new(map.num_rows, map.num_columns);
+    find_distances(map, start, end)[end]
+}
+
+/// Searches breadth first from `start` until it reaches `end`, and returns the number of steps
+/// to every point it reached.
+fn find_distances(map: &HeightMap, start: Point, end: Point) -> GridBuf<Option<u32>> {
+    let mut cache = GridBuf::new(map.grid.num_rows(), map.grid.num_columns(), None);
     cache[start] = Some(0);
     let mut queue = VecDeque::from([start]);
+    common::trace!("search", row = start.row, column = start.column);
 
-    while !cache.has(end) {
+    while cache[end].is_none() {
         if let Some(point) = queue.pop_back() {
             let height = map[point];
             let num_steps = cache[point].unwrap();
-            for next_point in map.surrounding_points(point) {
-                if !cache.has(next_point) && map[next_point] <= height + 1 {
+            common::trace!(
+                "expand",
+                row = point.row,
+                column = point.column,
+                height = char::from(height),
+                steps = num_steps
+            );
+            for next_point in map.grid.neighbours_4(point) {
+                if cache[next_point].is_none() && map[next_point] <= height + 1 {
                     cache[next_point] = Some(num_steps + 1);
+                    common::trace!(
+                        "reach",
+                        row = next_point.row,
+                        column = next_point.column,
+                        steps = num_steps + 1
+                    );
                     queue.push_front(next_point);
                 }
             }
         } else {
-            return None;
+            break;
         }
     }
 
-    Some(cache[end].unwrap())
+    cache
 }
 
-type Point = (usize, usize);
-
-struct HeightMap<'a> {
-    num_columns: usize,
-    num_rows: usize,
-    data: &'a [u8],
+/// The heights of the area around the start and end positions, from `a` to `z`.
+pub struct HeightMap<'a> {
+    data: &'a str,
+    grid: GridRef<'a>,
+    start: Point,
+    end: Point,
 }
 
-impl<'a> From<&'a [u8]> for HeightMap<'a> {
-    fn from(data: &'a [u8]) -> Self {
-        let num_columns = data.iter().position(|&byte| byte == b'\n').unwrap();
-        let num_rows = data.len() / num_columns;
-        Self {
-            num_columns,
-            num_rows,
+impl<'a> TryFrom<&'a str> for HeightMap<'a> {
+    type Error = ParseError;
+
+    fn try_from(data: &'a str) -> Result<Self, Self::Error> {
+        let grid = GridRef::parse(
+            data,
+            |byte| matches!(byte, b'a'..=b'z' | b'S' | b'E'),
+            "a height from `a` to `z`, `S` or `E`",
+            "heights",
+        )?;
+        let mut map = Self {
             data,
+            grid,
+            start: Point::new(0, 0),
+            end: Point::new(0, 0),
+        };
+        let mut start = None;
+        let mut end = None;
+        for point in map.grid.points() {
+            let marker = match map.grid[point] {
+                b'S' => &mut start,
+                b'E' => &mut end,
+                _ => continue,
+            };
+            if marker.replace(point).is_some() {
+                let marker = char::from(map.grid[point]);
+                return Err(map.error_at(point, &format!("a single `{marker}`")));
+            }
         }
+        let end_of_input = &data[data.len()..];
+        map.start =
+            start.ok_or_else(|| ParseError::at(data, end_of_input, "a start position `S`"))?;
+        map.end = end.ok_or_else(|| ParseError::at(data, end_of_input, "an end position `E`"))?;
+        Ok(map)
     }
 }
 
-impl<'a> Index<Point> for HeightMap<'a> {
+impl Index<Point> for HeightMap<'_> {
     type Output = u8;
 
-    fn index(&self, (row, column): Point) -> &Self::Output {
-        match &self.data[row * (self.num_columns + 1) + column] {
-            height @ b'a'..=b'z' => height,
+    fn index(&self, point: Point) -> &Self::Output {
+        match &self.grid[point] {
             b'S' => &b'a',
             b'E' => &b'z',
-            _ => panic!(),
+            height => height,
         }
     }
 }
 
 impl HeightMap<'_> {
-    fn all_points(&self) -> impl Iterator<Item = Point> + '_ {
-        (0..self.num_rows).flat_map(|row| (0..self.num_columns).map(move |column| (row, column)))
-    }
-
-    fn find(&self, byte: u8) -> Point {
-        self.all_points()
-            .find(|&(row, column)| self.data[row * (self.num_columns + 1) + column] == byte)
-            .unwrap()
+    fn error_at(&self, point: Point, expected: &str) -> ParseError {
+        let offset = point.row * (self.grid.num_columns() + 1) + point.column;
+        ParseError::at(self.data, &self.data[offset..=offset], expected)
     }
 
     fn find_lowest_points(&self) -> impl Iterator<Item = Point> + '_ {
-        self.all_points()
-            .filter(|&(row, column)| self.data[row * (self.num_columns + 1) + column] == b'a')
-    }
-
-    fn surrounding_points(&self, (row, column): Point) -> impl Iterator<Item = Point> + '_ {
-        let up = row.checked_sub(1).map(move |r| (r, column)).into_iter();
-        let down = Some(row + 1)
-            .filter(|&r| r < self.num_rows)
-            .map(move |r| (r, column))
-            .into_iter();
-        let left = column.checked_sub(1).map(move |c| (row, c)).into_iter();
-        let right = Some(column + 1)
-            .filter(|&c| c < self.num_columns)
-            .map(move |c| (row, c))
-            .into_iter();
-
-        up.chain(down).chain(left.chain(right))
-    }
-}
-
-struct Cache {
-    num_columns: usize,
-    data: Vec<Option<u32>>,
-}
-
-impl Cache {
-    fn new(num_rows: usize, num_columns: usize) -> Self {
-        Self {
-            num_columns,
-            data: vec![None; num_rows * num_columns],
-        }
-    }
-
-    fn has(&self, point: Point) -> bool {
-        self[point].is_some()
-    }
-}
-
-impl Index<Point> for Cache {
-    type Output = Option<u32>;
-
-    fn index(&self, (row, column): Point) -> &Self::Output {
-        &self.data[row * self.num_columns + column]
-    }
-}
-
-impl IndexMut<Point> for Cache {
-    fn index_mut(&mut self, (row, column): Point) -> &mut Self::Output {
-        &mut self.data[row * self.num_columns + column]
+        self.grid.points().filter(|&point| self.grid[point] == b'a')
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
-    const SAMPLE_INPUT: &[u8] = include_bytes!("../sample-input.txt");
-    const INPUT: &[u8] = include_bytes!("../input.txt");
+    const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
 
     #[test]
     fn test_part_1_sample() {
@@ -158,7 +187,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(INPUT), 350);
+        let Some(input) = real_input(12) else { return };
+        assert_eq!(
+            Answer::from(part_1(&input)),
+            expected_answer(12, 1, "input")
+        );
     }
 
     #[test]
@@ -168,6 +201,71 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(INPUT), 349);
+        let Some(input) = real_input(12) else { return };
+        assert_eq!(
+            Answer::from(part_2(&input)),
+            expected_answer(12, 2, "input")
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = try_part_1("Sab\nczE\nab").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "a row of 3 heights");
+
+        let error = try_part_1("Sab\ncz?\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "`?`");
+
+        let error = try_part_2("SaE\nabS\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a single `S`");
+
+        let error = try_part_1("Sab\nabc\n").unwrap_err();
+        assert_eq!(error.found, "end of input");
+        assert_eq!(error.expected, "an end position `E`");
+
+        let error = try_part_1("SaxE\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "a route from `S` to `E`");
+    }
+
+    #[test]
+    fn test_whitespace() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let (_, events) = trace::collect(|| part_1(SAMPLE_INPUT));
+        assert_eq!(events[0].to_string(), "search row=0 column=0");
+        assert_eq!(
+            events[1].to_string(),
+            r#"expand row=0 column=0 height="a" steps=0"#
+        );
+        assert_eq!(
+            events.last().unwrap().to_string(),
+            "reach row=2 column=5 steps=31"
+        );
+        assert_eq!(
+            events.iter().filter(|event| event.name == "search").count(),
+            1
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_mutated_input(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day12>(&input);
+        }
+
+        #[test]
+        fn test_random_input(input in ".*") {
+            solve_both::<Day12>(&input);
+        }
     }
 }
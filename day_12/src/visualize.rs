@@ -0,0 +1,111 @@
+use crate::{find_distances, find_shortest_route, Day12, HeightMap};
+use common::{
+    visualize::{Color, Frame, Visualize},
+    ParseError, Solution,
+};
+use grid::{GridBuf, Point};
+
+impl Visualize for Day12 {
+    /// Draws the map as the search spreads out from the start, one step further in each frame,
+    /// and then the shortest route. For part 2, the search starts from the lowest point with the
+    /// shortest route.
+    fn frames(part: u8, input: &str) -> Result<Vec<Frame>, ParseError> {
+        let map = HeightMap::try_from(input)?;
+        let start = if part == 1 {
+            Day12::solve_1(input, &map)?;
+            map.start
+        } else {
+            Day12::solve_2(input, &map)?;
+            map.find_lowest_points()
+                .min_by_key(|&start| find_shortest_route(&map, start, map.end).unwrap_or(u32::MAX))
+                .unwrap()
+        };
+        let distances = find_distances(&map, start, map.end);
+        let num_steps = distances[map.end].unwrap();
+
+        let mut frames: Vec<Frame> = (0..=num_steps)
+            .map(|step| {
+                let mut frame = draw_map(&map, |point| match distances[point] {
+                    Some(distance) if distance < step => Color::Dim,
+                    Some(distance) if distance == step => Color::Yellow,
+                    _ => Color::Default,
+                });
+                frame.caption = format!("{step} steps from the start");
+                frame
+            })
+            .collect();
+
+        let route = find_route(&map, &distances);
+        let mut frame = draw_map(&map, |point| {
+            if route.contains(&point) {
+                Color::Green
+            } else {
+                Color::Dim
+            }
+        });
+        frame.caption = format!("the shortest route takes {num_steps} steps");
+        frames.push(frame);
+        Ok(frames)
+    }
+}
+
+fn draw_map(map: &HeightMap, mut color: impl FnMut(Point) -> Color) -> Frame {
+    let mut frame = Frame::new(map.grid.num_rows(), map.grid.num_columns());
+    for point in map.grid.points() {
+        let glyph = char::from(map.grid[point]);
+        let color = if glyph == 'S' || glyph == 'E' {
+            Color::Red
+        } else {
+            color(point)
+        };
+        frame.set(point.row, point.column, glyph, color);
+    }
+    frame
+}
+
+/// Walks back from the end to a point one step closer to the start every time, which leads
+/// along one of the shortest routes.
+fn find_route(map: &HeightMap, distances: &GridBuf<Option<u32>>) -> Vec<Point> {
+    let mut route = vec![map.end];
+    let mut point = map.end;
+    while let Some(distance @ 1..) = distances[point] {
+        point = map
+            .grid
+            .neighbours_4(point)
+            .find(|&previous| {
+                distances[previous] == Some(distance - 1) && map[point] <= map[previous] + 1
+            })
+            .unwrap();
+        route.push(point);
+    }
+    route.reverse();
+    route
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::visualize::{Ascii, Renderer};
+
+    #[test]
+    fn test_frames() {
+        let input = include_str!("../sample-input.txt");
+        let frames = Day12::frames(1, input).unwrap();
+        assert_eq!(frames.len(), 33);
+        assert_eq!(frames[1].get(0, 1).color, Color::Yellow);
+        assert_eq!(frames[1].get(1, 0).color, Color::Yellow);
+        assert_eq!(frames[1].get(1, 1).color, Color::Default);
+        assert_eq!(frames[32].caption, "the shortest route takes 31 steps");
+        let num_on_route = frames[32]
+            .rows()
+            .flatten()
+            .filter(|cell| cell.color == Color::Green)
+            .count();
+        assert_eq!(num_on_route, 30);
+        assert_eq!(Ascii.render(&frames[32]).lines().next(), Some("Sabqponm"));
+
+        let frames = Day12::frames(2, input).unwrap();
+        assert_eq!(frames.len(), 31);
+        assert!(Day12::frames(1, "SaxE\n").is_err());
+    }
+}
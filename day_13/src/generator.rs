@@ -0,0 +1,71 @@
+use crate::parse_packet;
+use common::rng::Rng;
+use std::cmp::Ordering::Equal;
+
+/// Generates about `size` bytes of pairs of random packets, nested up to four lists deep. The
+/// packets of a pair are never equal, and no packet equals a divider packet.
+#[must_use]
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size + 200);
+    while input.len() < size {
+        if !input.is_empty() {
+            input.push('\n');
+        }
+        let left = random_packet(&mut rng);
+        let left_packet = parse_packet(&left, &left).unwrap();
+        // Packets like `[1]` and `[[1]]` differ, but are in neither the right nor the wrong order.
+        let right = loop {
+            let right = random_packet(&mut rng);
+            if parse_packet(&right, &right).unwrap().cmp(&left_packet) != Equal {
+                break right;
+            }
+        };
+        input.push_str(&left);
+        input.push('\n');
+        input.push_str(&right);
+        input.push('\n');
+    }
+    input
+}
+
+fn random_packet(rng: &mut Rng) -> String {
+    loop {
+        let mut packet = String::new();
+        write_list(rng, &mut packet, 1);
+        if !parse_packet(&packet, &packet).unwrap().is_divider() {
+            return packet;
+        }
+    }
+}
+
+fn write_list(rng: &mut Rng, packet: &mut String, depth: usize) {
+    packet.push('[');
+    for i in 0..rng.range(0..=5) {
+        if i > 0 {
+            packet.push(',');
+        }
+        if depth < 4 && rng.percent(30) {
+            write_list(rng, packet, depth + 1);
+        } else {
+            packet.push_str(&rng.range(0..=10).to_string());
+        }
+    }
+    packet.push(']');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day13;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(10_000, 1);
+        assert!((10_000..10_200).contains(&input.len()));
+        assert_eq!(input, generate(10_000, 1));
+        assert!(Day13::try_part_1(&input).is_ok());
+        assert!(Day13::try_part_2(&input).is_ok());
+    }
+}
@@ -1,50 +1,109 @@
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_panics_doc)]
 
-use std::{
-    cmp::{
-        Ordering,
-        Ordering::{Equal, Greater, Less},
-    },
-    str::FromStr,
+pub mod generator;
+
+use common::{ParseError, Solution};
+use std::cmp::{
+    Ordering,
+    Ordering::{Equal, Greater, Less},
 };
 use ListOrInteger::{Integer, List};
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .map(|i| {
-            let (left, right) = i.split_once('\n').unwrap();
-            (
-                left.parse::<ListOrInteger>().unwrap(),
-                right.parse().unwrap(),
-            )
-        })
-        .enumerate()
-        .filter_map(|(index, (left, right))| match left.cmp(&right) {
-            Less => Some(index + 1),
-            Equal => panic!(),
-            Greater => None,
-        })
-        .sum()
+    try_part_1(input).unwrap_or_else(|error| panic!("{error}"))
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    let mut packets: Vec<_> = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.parse().unwrap())
-        .collect();
-    packets.push(ListOrInteger::divider(2));
-    packets.push(ListOrInteger::divider(6));
-    packets.sort();
-    packets
-        .iter()
-        .enumerate()
-        .filter_map(|(index, packet)| packet.is_divider().then_some(index + 1))
-        .product()
+    try_part_2(input).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// # Errors
+///
+/// Returns an error if a packet cannot be parsed, if a pair does not consist of exactly two
+/// packets, or if the two packets of a pair are equal.
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Day13::try_part_1(input)
+}
+
+/// # Errors
+///
+/// Returns an error if a packet cannot be parsed, or if a pair does not consist of exactly two
+/// packets.
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Day13::try_part_2(input)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Parsed<'a> = Pairs<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Pairs<'_>, ParseError> {
+        let pairs = input
+            .split("\n\n")
+            .map(|pair| {
+                let mut lines = pair.lines();
+                let mut next_packet = || {
+                    let line = lines.next().unwrap_or(&pair[pair.len()..]);
+                    parse_packet(input, line)
+                };
+                let left = next_packet()?;
+                let right = next_packet()?;
+                if let Some(line) = lines.find(|line| !line.is_empty()) {
+                    return Err(ParseError::at(input, line, "a blank line"));
+                }
+                Ok((pair, left, right))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Pairs { pairs })
+    }
+
+    fn solve_1(input: &str, pairs: &Pairs<'_>) -> Result<usize, ParseError> {
+        let mut sum = 0;
+        for (index, (pair, left, right)) in pairs.pairs.iter().enumerate() {
+            let order = left.cmp(right);
+            common::trace!("pair", index = index + 1, order = order);
+            match order {
+                Less => sum += index + 1,
+                Equal => {
+                    return Err(ParseError::at(
+                        input,
+                        pair.trim_end(),
+                        "a pair of different packets",
+                    ))
+                }
+                Greater => (),
+            }
+        }
+        Ok(sum)
+    }
+
+    fn solve_2(_input: &str, pairs: &Pairs<'_>) -> Result<usize, ParseError> {
+        let mut packets: Vec<_> = pairs
+            .pairs
+            .iter()
+            .flat_map(|(_, left, right)| [left, right])
+            .collect();
+        let dividers = [ListOrInteger::divider(2), ListOrInteger::divider(6)];
+        packets.extend(&dividers);
+        packets.sort();
+        Ok(packets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, packet)| packet.is_divider().then_some(index + 1))
+            .product())
+    }
+}
+
+/// The pairs of packets, each with the lines it was parsed from.
+pub struct Pairs<'a> {
+    pairs: Vec<(&'a str, ListOrInteger, ListOrInteger)>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -72,15 +131,25 @@ impl PartialOrd for ListOrInteger {
 impl Ord for ListOrInteger {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            (Integer(l), Integer(r)) => l.cmp(r),
+            (Integer(l), Integer(r)) => {
+                let order = l.cmp(r);
+                common::trace!("compare", left = *l, right = *r, order = order);
+                order
+            }
             (List(l), List(r)) => cmp_lists(l, r),
-            (Integer(l), List(r)) => cmp_lists(&vec![Integer(*l)], r),
-            (List(l), Integer(r)) => cmp_lists(l, &vec![Integer(*r)]),
+            (Integer(l), List(r)) => {
+                common::trace!("wrap", side = "left", integer = *l);
+                cmp_lists(&[Integer(*l)], r)
+            }
+            (List(l), Integer(r)) => {
+                common::trace!("wrap", side = "right", integer = *r);
+                cmp_lists(l, &[Integer(*r)])
+            }
         }
     }
 }
 
-fn cmp_lists(left: &Vec<ListOrInteger>, right: &Vec<ListOrInteger>) -> Ordering {
+fn cmp_lists(left: &[ListOrInteger], right: &[ListOrInteger]) -> Ordering {
     let left_len = left.len();
     let right_len = right.len();
 
@@ -92,60 +161,93 @@ fn cmp_lists(left: &Vec<ListOrInteger>, right: &Vec<ListOrInteger>) -> Ordering
         }
     }
 
-    left_len.cmp(&right_len)
+    let order = left_len.cmp(&right_len);
+    common::trace!(
+        "compare_lengths",
+        left = left_len,
+        right = right_len,
+        order = order
+    );
+    order
 }
 
-impl FromStr for ListOrInteger {
-    type Err = ();
+/// How deeply lists may be nested. Parsing and comparing packets recurse into nested lists, so
+/// without a limit a long enough line of `[` would overflow the stack.
+const MAX_DEPTH: usize = 100;
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(parse_list_or_integer(input).unwrap().1)
+/// Parses a packet, which is a list that takes up a whole line.
+fn parse_packet(input: &str, line: &str) -> Result<ListOrInteger, ParseError> {
+    let (list, rest) = parse_list(input, line, 1)?;
+    if rest.is_empty() {
+        Ok(List(list))
+    } else {
+        Err(ParseError::at(input, rest, "end of line"))
     }
 }
 
-fn parse_list(input: &str) -> Option<(usize, Vec<ListOrInteger>)> {
-    input.strip_prefix('[')?;
-    let mut i = 1;
-
+/// Parses a list at the start of `rest`, and returns it along with whatever follows it. `depth`
+/// counts the lists it is nested in, itself included.
+fn parse_list<'a>(
+    input: &str,
+    rest: &'a str,
+    depth: usize,
+) -> Result<(Vec<ListOrInteger>, &'a str), ParseError> {
+    if depth > MAX_DEPTH {
+        return Err(ParseError::at_char(
+            input,
+            rest,
+            format!("a list nested at most {MAX_DEPTH} deep"),
+        ));
+    }
+    let mut rest = rest
+        .strip_prefix('[')
+        .ok_or_else(|| ParseError::at_char(input, rest, "`[`"))?;
     let mut list = Vec::new();
-    while let Some((num_chars, list_or_integer)) = parse_list_or_integer(&input[i..]) {
+    if let Some(rest) = rest.strip_prefix(']') {
+        return Ok((list, rest));
+    }
+
+    loop {
+        let (list_or_integer, after_value) = parse_list_or_integer(input, rest, depth)?;
         list.push(list_or_integer);
-        i += num_chars;
-        if input[i..].starts_with(',') {
-            i += 1;
+        if let Some(after_comma) = after_value.strip_prefix(',') {
+            rest = after_comma;
+        } else if let Some(after_list) = after_value.strip_prefix(']') {
+            return Ok((list, after_list));
+        } else {
+            return Err(ParseError::at_char(input, after_value, "`,` or `]`"));
         }
     }
-
-    input[i..].strip_prefix(']')?;
-    i += 1;
-
-    Some((i, list))
 }
 
-fn parse_integer(input: &str) -> Option<(usize, u32)> {
-    let num_digits = input.chars().take_while(char::is_ascii_digit).count();
-    if let Ok(digit) = input[0..num_digits].parse() {
-        Some((num_digits, digit))
-    } else {
-        None
+fn parse_list_or_integer<'a>(
+    input: &str,
+    rest: &'a str,
+    depth: usize,
+) -> Result<(ListOrInteger, &'a str), ParseError> {
+    if rest.starts_with('[') {
+        let (list, rest) = parse_list(input, rest, depth + 1)?;
+        return Ok((List(list), rest));
     }
-}
 
-fn parse_list_or_integer(input: &str) -> Option<(usize, ListOrInteger)> {
-    if let Some((num_chars, list)) = parse_list(input) {
-        Some((num_chars, List(list)))
-    } else if let Some((num_chars, integer)) = parse_integer(input) {
-        Some((num_chars, Integer(integer)))
-    } else {
-        None
+    let num_digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if num_digits == 0 {
+        return Err(ParseError::at_char(input, rest, "`[` or an integer"));
     }
+    let (digits, rest) = rest.split_at(num_digits);
+    let integer = digits
+        .parse()
+        .map_err(|_| ParseError::at(input, digits, "an integer that fits in 32 bits"))?;
+    Ok((Integer(integer), rest))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::fuzz::{mutated_input, solve_both, whitespace_variants};
+    use common::{input::real_input, manifest::expected_answer, trace, Answer};
+    use proptest::prelude::*;
 
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE_INPUT: &str = include_str!("../sample-input.txt");
 
     #[test]
@@ -155,7 +257,11 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(INPUT), 5_292);
+        let Some(input) = real_input(13) else { return };
+        assert_eq!(
+            Answer::from(part_1(&input)),
+            expected_answer(13, 1, "input")
+        );
     }
 
     #[test]
@@ -165,6 +271,106 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2(INPUT), 23_868);
+        let Some(input) = real_input(13) else { return };
+        assert_eq!(
+            Answer::from(part_2(&input)),
+            expected_answer(13, 2, "input")
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = try_part_1("[1,[2,3]\n[1]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert_eq!(error.expected, "`,` or `]`");
+        assert_eq!(error.found, "end of line");
+
+        let error = try_part_2("[1,[2,x]]\n[1]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.found, "`x`");
+
+        let error = try_part_1("[1]\n[2]\n\n[3]\n").unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.found, "end of input");
+
+        let error = try_part_1("[1]\n[2]]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.expected, "end of line");
+
+        let error = try_part_1("[1]\n[2]\n\n[[3]]\n[[3]]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.expected, "a pair of different packets");
+
+        let error = try_part_2("[99999999999]\n").unwrap_err();
+        assert_eq!(error.found, "`99999999999`");
+
+        let input = format!("{}\n[1]\n", "[".repeat(100_000));
+        let error = try_part_1(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 101));
+        assert_eq!(error.expected, "a list nested at most 100 deep");
+    }
+
+    #[test]
+    fn test_whitespace() {
+        for input in whitespace_variants(SAMPLE_INPUT) {
+            assert_eq!(part_1(&input), part_1(SAMPLE_INPUT));
+            assert_eq!(part_2(&input), part_2(SAMPLE_INPUT));
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let (_, events) = trace::collect(|| part_1("[1,[2]]\n[1,3]\n\n[]\n[[]]\n"));
+        let events: Vec<_> = events.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            events,
+            [
+                r#"compare left=1 right=1 order="equal""#,
+                r#"wrap side="right" integer=3"#,
+                r#"compare left=2 right=3 order="less""#,
+                r#"pair index=1 order="less""#,
+                r#"compare_lengths left=0 right=1 order="less""#,
+                r#"pair index=2 order="less""#,
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_mutated_input(input in mutated_input(generator::generate, 2_000)) {
+            solve_both::<Day13>(&input);
+        }
+
+        #[test]
+        fn test_random_input(input in ".*") {
+            solve_both::<Day13>(&input);
+        }
+
+        #[test]
+        fn test_round_trip(packet in packet()) {
+            let line = format_packet(&packet);
+            prop_assert_eq!(parse_packet(&line, &line), Ok(packet));
+        }
+    }
+
+    fn packet() -> impl Strategy<Value = ListOrInteger> {
+        let leaf = any::<u32>().prop_map(Integer);
+        leaf.prop_recursive(8, 64, 8, |inner| {
+            prop::collection::vec(inner, 0..8).prop_map(List)
+        })
+        .prop_map(|packet| match packet {
+            Integer(_) => List(vec![packet]),
+            list @ List(_) => list,
+        })
+    }
+
+    fn format_packet(packet: &ListOrInteger) -> String {
+        match packet {
+            Integer(integer) => integer.to_string(),
+            List(list) => {
+                let items: Vec<_> = list.iter().map(format_packet).collect();
+                format!("[{}]", items.join(","))
+            }
+        }
     }
 }
@@ -0,0 +1,73 @@
+/*
+ * The solutions as a C library, built from the `aoc_ffi` crate as `libaoc_ffi`.
+ *
+ * Every function takes the puzzle input as `len` bytes of UTF-8 at `input`, which may be null
+ * if `len` is 0, and returns an `aoc_result` that must be freed with `aoc_result_free`. Each
+ * call is independent, so the functions can be called from any thread.
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum aoc_kind {
+    AOC_INTEGER = 0,
+    AOC_TEXT = 1,
+    AOC_ERROR = 2,
+} aoc_kind;
+
+typedef struct aoc_result {
+    aoc_kind kind;
+    /* The answer if `kind` is `AOC_INTEGER`, 0 otherwise. */
+    int64_t integer;
+    /*
+     * The answer if `kind` is `AOC_TEXT`, or the message if it is `AOC_ERROR`, as a
+     * NUL-terminated UTF-8 string owned by the result. Null for integers.
+     */
+    char *text;
+} aoc_result;
+
+/* Solves a part (1 or 2) of a day given by number, or returns an error if there is no such part. */
+aoc_result aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len);
+
+/* Frees the text of a result and sets it to null. Does nothing if `result` is null. */
+void aoc_result_free(aoc_result *result);
+
+aoc_result aoc_day_01_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_01_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_02_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_02_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_03_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_03_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_04_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_04_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_05_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_05_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_06_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_06_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_07_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_07_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_08_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_08_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_09_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_09_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_10_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_10_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_11_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_11_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_12_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_12_part_2(const uint8_t *input, size_t len);
+aoc_result aoc_day_13_part_1(const uint8_t *input, size_t len);
+aoc_result aoc_day_13_part_2(const uint8_t *input, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
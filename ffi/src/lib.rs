@@ -0,0 +1,275 @@
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)]
+
+use common::{Answer, ParseError};
+use std::{
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice, str,
+};
+
+/// What an [`AocResult`] holds. Declared as `aoc_kind` in `include/aoc.h`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocKind {
+    Integer = 0,
+    Text = 1,
+    Error = 2,
+}
+
+/// The answer to a part, or why there is none, as returned to C. Declared as `aoc_result` in
+/// `include/aoc.h`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocResult {
+    pub kind: AocKind,
+    /// The answer if `kind` is [`AocKind::Integer`], 0 otherwise.
+    pub integer: i64,
+    /// The answer if `kind` is [`AocKind::Text`], or the message if it is [`AocKind::Error`], as
+    /// a NUL-terminated UTF-8 string owned by the result. Null for integers.
+    pub text: *mut c_char,
+}
+
+impl AocResult {
+    fn text(kind: AocKind, text: &str) -> Self {
+        // Errors quote the input, which C cannot have passed with NULs in it anyway.
+        let text = CString::new(text.replace('\0', "\u{fffd}")).unwrap();
+        Self {
+            kind,
+            integer: 0,
+            text: text.into_raw(),
+        }
+    }
+
+    fn error(message: &str) -> Self {
+        Self::text(AocKind::Error, message)
+    }
+}
+
+impl From<Answer> for AocResult {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Integer(integer) => Self {
+                kind: AocKind::Integer,
+                integer,
+                text: ptr::null_mut(),
+            },
+            Answer::Text(text) => Self::text(AocKind::Text, &text),
+        }
+    }
+}
+
+type Solver = fn(u8, &str) -> Result<Answer, ParseError>;
+
+/// Solves a part of an input passed from C. Every way this can fail becomes an error result,
+/// including a panic, which must not unwind into C.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or `len` must be 0.
+unsafe fn solve(solver: Solver, part: u8, input: *const u8, len: usize) -> AocResult {
+    let input = if len == 0 {
+        &[]
+    } else if input.is_null() {
+        return AocResult::error("the input is null");
+    } else {
+        // SAFETY: the caller promises that `input` points to `len` bytes.
+        unsafe { slice::from_raw_parts(input, len) }
+    };
+    let Ok(input) = str::from_utf8(input) else {
+        return AocResult::error("the input is not valid UTF-8");
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| solver(part, input))) {
+        Ok(Ok(answer)) => answer.into(),
+        Ok(Err(error)) => AocResult::error(&format!("invalid input at {error}")),
+        Err(_) => AocResult::error("the solution panicked"),
+    }
+}
+
+/// Exports both parts of every day as `aoc_day_NN_part_N`, and puts the days in a table for
+/// [`aoc_solve`].
+macro_rules! days {
+    ($($day:ident::$solution:ident => $part_1:ident, $part_2:ident;)*) => {
+        const SOLVERS: &[Solver] = &[$(<$day::$solution as common::Solution>::try_solve),*];
+
+        #[cfg(test)]
+        const FUNCTIONS: &[&str] = &[$(stringify!($part_1), stringify!($part_2)),*];
+
+        $(
+            #[doc = concat!("Solves part 1 of ", stringify!($day), ".")]
+            ///
+            /// # Safety
+            ///
+            /// `input` must point to `len` readable bytes, or `len` must be 0.
+            #[no_mangle]
+            pub unsafe extern "C" fn $part_1(input: *const u8, len: usize) -> AocResult {
+                // SAFETY: passed on from the caller.
+                unsafe { solve(<$day::$solution as common::Solution>::try_solve, 1, input, len) }
+            }
+
+            #[doc = concat!("Solves part 2 of ", stringify!($day), ".")]
+            ///
+            /// # Safety
+            ///
+            /// `input` must point to `len` readable bytes, or `len` must be 0.
+            #[no_mangle]
+            pub unsafe extern "C" fn $part_2(input: *const u8, len: usize) -> AocResult {
+                // SAFETY: passed on from the caller.
+                unsafe { solve(<$day::$solution as common::Solution>::try_solve, 2, input, len) }
+            }
+        )*
+    };
+}
+
+days! {
+    day_01::Day01 => aoc_day_01_part_1, aoc_day_01_part_2;
+    day_02::Day02 => aoc_day_02_part_1, aoc_day_02_part_2;
+    day_03::Day03 => aoc_day_03_part_1, aoc_day_03_part_2;
+    day_04::Day04 => aoc_day_04_part_1, aoc_day_04_part_2;
+    day_05::Day05 => aoc_day_05_part_1, aoc_day_05_part_2;
+    day_06::Day06 => aoc_day_06_part_1, aoc_day_06_part_2;
+    day_07::Day07 => aoc_day_07_part_1, aoc_day_07_part_2;
+    day_08::Day08 => aoc_day_08_part_1, aoc_day_08_part_2;
+    day_09::Day09 => aoc_day_09_part_1, aoc_day_09_part_2;
+    day_10::Day10 => aoc_day_10_part_1, aoc_day_10_part_2;
+    day_11::Day11 => aoc_day_11_part_1, aoc_day_11_part_2;
+    day_12::Day12 => aoc_day_12_part_1, aoc_day_12_part_2;
+    day_13::Day13 => aoc_day_13_part_1, aoc_day_13_part_2;
+}
+
+/// Solves a part of a day given by number, for callers that loop over the days.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or `len` must be 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u8, part: u8, input: *const u8, len: usize) -> AocResult {
+    let Some(&solver) = usize::from(day)
+        .checked_sub(1)
+        .and_then(|index| SOLVERS.get(index))
+    else {
+        return AocResult::error(&format!(
+            "there is no day {day}, expected 1 to {}",
+            SOLVERS.len()
+        ));
+    };
+    if part != 1 && part != 2 {
+        return AocResult::error(&format!("there is no part {part}, expected 1 or 2"));
+    }
+    // SAFETY: passed on from the caller.
+    unsafe { solve(solver, part, input, len) }
+}
+
+/// Frees the text of a result and sets it to null, so that freeing a result twice is harmless.
+///
+/// # Safety
+///
+/// `result` must be null or point to a result returned by this library.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_result_free(result: *mut AocResult) {
+    // SAFETY: the caller promises that `result` is null or points to a result from here, whose
+    // text was made with `CString::into_raw` if it is not null.
+    unsafe {
+        if let Some(result) = result.as_mut() {
+            if !result.text.is_null() {
+                drop(CString::from_raw(result.text));
+                result.text = ptr::null_mut();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    /// Copies a result into Rust types and frees it.
+    fn take(mut result: AocResult) -> (AocKind, i64, Option<String>) {
+        let text = (!result.text.is_null()).then(|| {
+            // SAFETY: the text of a result is a valid C string.
+            let text = unsafe { CStr::from_ptr(result.text) };
+            text.to_str().unwrap().to_owned()
+        });
+        // SAFETY: the result came from this library.
+        unsafe { aoc_result_free(ptr::addr_of_mut!(result)) };
+        assert!(result.text.is_null());
+        (result.kind, result.integer, text)
+    }
+
+    fn solve_bytes(day: u8, part: u8, input: &[u8]) -> (AocKind, i64, Option<String>) {
+        // SAFETY: the slice has `len` bytes.
+        take(unsafe { aoc_solve(day, part, input.as_ptr(), input.len()) })
+    }
+
+    #[test]
+    fn test_answers() {
+        let input = include_str!("../../day_02/sample-input.txt");
+        // SAFETY: the string has `len` bytes.
+        let result = unsafe { aoc_day_02_part_2(input.as_ptr(), input.len()) };
+        assert_eq!(take(result), (AocKind::Integer, 12, None));
+
+        let input = include_bytes!("../../day_05/sample-input.txt");
+        assert_eq!(
+            solve_bytes(5, 1, input),
+            (AocKind::Text, 0, Some(String::from("CMZ")))
+        );
+        let input = include_bytes!("../../day_10/sample-input.txt");
+        let (kind, _, text) = solve_bytes(10, 2, input);
+        assert_eq!(kind, AocKind::Text);
+        assert!(text
+            .unwrap()
+            .starts_with("##..##..##..##..##..##..##..##..##..##..\n"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let (kind, _, message) = solve_bytes(2, 1, b"A Y\nB W\n");
+        assert_eq!(kind, AocKind::Error);
+        assert!(message
+            .unwrap()
+            .starts_with("invalid input at line 2, column 3: "));
+
+        let (kind, _, message) = solve_bytes(1, 1, b"1000\n\xff\n");
+        assert_eq!(kind, AocKind::Error);
+        assert_eq!(message.unwrap(), "the input is not valid UTF-8");
+
+        // SAFETY: a null input with a length of 0 is allowed.
+        let result = unsafe { aoc_solve(1, 1, ptr::null(), 0) };
+        assert_eq!(take(result), (AocKind::Integer, 0, None));
+        // SAFETY: a null input is reported, whatever its length.
+        let result = unsafe { aoc_solve(1, 1, ptr::null(), 5) };
+        assert_eq!(take(result).2.unwrap(), "the input is null");
+
+        assert_eq!(
+            solve_bytes(14, 1, b"").2.unwrap(),
+            "there is no day 14, expected 1 to 13"
+        );
+        assert_eq!(solve_bytes(0, 1, b"").0, AocKind::Error);
+        assert_eq!(
+            solve_bytes(1, 3, b"").2.unwrap(),
+            "there is no part 3, expected 1 or 2"
+        );
+    }
+
+    #[test]
+    fn test_free_null() {
+        // SAFETY: null is allowed.
+        unsafe { aoc_result_free(ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_header() {
+        let header = include_str!("../include/aoc.h");
+        for function in FUNCTIONS.iter().chain(&["aoc_solve", "aoc_result_free"]) {
+            assert!(
+                header.contains(&format!(" {function}(")),
+                "{function} is missing from include/aoc.h"
+            );
+        }
+        assert_eq!(
+            header.matches("aoc_result aoc_day_").count(),
+            FUNCTIONS.len()
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Builds `harness.c` against the `cdylib` with the system C compiler, and runs it.
+//!
+//! This lives outside the crate, unlike the other tests, because only integration tests are
+//! built after the `cdylib`.
+
+use std::{
+    env::consts::{DLL_PREFIX, DLL_SUFFIX},
+    env::current_exe,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The directory with the library in it, which is the one above the test executable in `deps`.
+fn library_dir() -> PathBuf {
+    let library = format!("{DLL_PREFIX}aoc_ffi{DLL_SUFFIX}");
+    current_exe()
+        .unwrap()
+        .ancestors()
+        .skip(1)
+        .take(2)
+        .find(|dir| dir.join(&library).exists())
+        .unwrap_or_else(|| panic!("{library} was not built next to the tests"))
+        .to_path_buf()
+}
+
+#[test]
+fn test_c_harness() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let library_dir = library_dir();
+    let harness = Path::new(env!("CARGO_TARGET_TMPDIR")).join("aoc_ffi_harness");
+
+    let compiled = Command::new("cc")
+        .arg(manifest_dir.join("tests/harness.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .args(["-laoc_ffi", "-std=c99", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&harness)
+        .status();
+    let Ok(compiled) = compiled else {
+        eprintln!("skipping the C harness: `cc` was not found");
+        return;
+    };
+    assert!(compiled.success(), "harness.c did not compile");
+
+    let workspace = manifest_dir.parent().unwrap();
+    let output = Command::new(&harness)
+        .args(
+            ["day_02", "day_05", "day_10"].map(|day| workspace.join(day).join("sample-input.txt")),
+        )
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "the C harness failed:\n{stderr}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "all checks passed\n"
+    );
+}
@@ -0,0 +1,86 @@
+/*
+ * Calls the library the way a C program would. Built and run by `c.rs`, with the samples to
+ * solve passed as arguments: day 2, day 5 and day 10.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (file == NULL) {
+        perror(path);
+        exit(2);
+    }
+    fseek(file, 0, SEEK_END);
+    *len = (size_t)ftell(file);
+    rewind(file);
+    char *contents = malloc(*len);
+    if (fread(contents, 1, *len, file) != *len) {
+        perror(path);
+        exit(2);
+    }
+    fclose(file);
+    return contents;
+}
+
+static void check(int ok, const char *what, aoc_result *result) {
+    if (!ok) {
+        fprintf(stderr, "FAILED: %s (kind %d, integer %lld, text %s)\n", what, result->kind,
+                (long long)result->integer, result->text == NULL ? "null" : result->text);
+        failures++;
+    }
+    aoc_result_free(result);
+    if (result->text != NULL) {
+        fprintf(stderr, "FAILED: %s: the text is not null after freeing\n", what);
+        failures++;
+    }
+}
+
+int main(int argc, char **argv) {
+    if (argc != 4) {
+        fprintf(stderr, "usage: %s DAY_02_SAMPLE DAY_05_SAMPLE DAY_10_SAMPLE\n", argv[0]);
+        return 2;
+    }
+    size_t len;
+
+    char *day_02 = read_file(argv[1], &len);
+    aoc_result result = aoc_day_02_part_1((const uint8_t *)day_02, len);
+    check(result.kind == AOC_INTEGER && result.integer == 15 && result.text == NULL,
+          "day 2 part 1 is 15", &result);
+    result = aoc_solve(2, 2, (const uint8_t *)day_02, len);
+    check(result.kind == AOC_INTEGER && result.integer == 12, "day 2 part 2 is 12", &result);
+    free(day_02);
+
+    char *day_05 = read_file(argv[2], &len);
+    result = aoc_day_05_part_1((const uint8_t *)day_05, len);
+    check(result.kind == AOC_TEXT && strcmp(result.text, "CMZ") == 0, "day 5 part 1 is CMZ",
+          &result);
+    free(day_05);
+
+    char *day_10 = read_file(argv[3], &len);
+    result = aoc_day_10_part_2((const uint8_t *)day_10, len);
+    check(result.kind == AOC_TEXT && strncmp(result.text, "##..##..", 8) == 0,
+          "day 10 part 2 is drawn", &result);
+    free(day_10);
+
+    const char *invalid = "A Y\nB W\n";
+    result = aoc_day_02_part_1((const uint8_t *)invalid, strlen(invalid));
+    check(result.kind == AOC_ERROR && strstr(result.text, "line 2, column 3") != NULL,
+          "invalid day 2 input is an error", &result);
+
+    result = aoc_solve(99, 1, NULL, 0);
+    check(result.kind == AOC_ERROR && result.text != NULL, "day 99 is an error", &result);
+
+    aoc_result_free(NULL);
+
+    if (failures == 0) {
+        puts("all checks passed");
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use common::Solution;
+use day_05::Day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = Day05::try_part_1(input);
+        let _ = Day05::try_part_2(input);
+    }
+});
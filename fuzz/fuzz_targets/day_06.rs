@@ -0,0 +1,12 @@
+#![no_main]
+
+use common::Solution;
+use day_06::Day06;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = Day06::try_part_1(input);
+        let _ = Day06::try_part_2(input);
+    }
+});
@@ -0,0 +1,336 @@
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::missing_panics_doc)]
+
+mod point;
+
+pub use point::{Direction, Point};
+
+use common::ParseError;
+use std::{
+    fmt::{Display, Formatter, Result as FormatResult},
+    iter,
+    ops::{Deref, DerefMut, Index, IndexMut},
+};
+
+/// A rectangular grid of cells, stored row by row in `cells`.
+///
+/// Rows may be separated by padding, which is how a grid can borrow the lines of a puzzle input
+/// with their newlines in between. See [`GridRef`] and [`GridBuf`] for the two usual variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<C> {
+    cells: C,
+    num_rows: usize,
+    num_columns: usize,
+    stride: usize,
+}
+
+/// A grid that borrows its cells, usually from the puzzle input.
+pub type GridRef<'a, T = u8> = Grid<&'a [T]>;
+
+/// A grid that owns its cells.
+pub type GridBuf<T> = Grid<Vec<T>>;
+
+impl<'a> GridRef<'a> {
+    /// Borrows a grid from lines of text of equal length, where every byte is a cell. A trailing
+    /// newline is allowed. `expected_cell` describes a valid cell and `cells` names them in the
+    /// plural, for the error reports.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a byte is not a valid cell, or if the lines differ in length.
+    pub fn parse(
+        text: &'a str,
+        mut is_cell: impl FnMut(u8) -> bool,
+        expected_cell: &str,
+        cells: &str,
+    ) -> Result<Self, ParseError> {
+        let num_columns = text.find('\n').unwrap_or(text.len());
+        if num_columns == 0 {
+            return Err(ParseError::at(
+                text,
+                &text[..0],
+                format!("a row of {cells}"),
+            ));
+        }
+        let mut num_rows = 0;
+        for row in text.strip_suffix('\n').unwrap_or(text).split('\n') {
+            if let Some(i) = row.bytes().position(|byte| !is_cell(byte)) {
+                return Err(ParseError::at_char(text, &row[i..], expected_cell));
+            } else if row.len() != num_columns {
+                return Err(ParseError::at(
+                    text,
+                    row,
+                    format!("a row of {num_columns} {cells}"),
+                ));
+            }
+            num_rows += 1;
+        }
+        Ok(Self {
+            cells: text.as_bytes(),
+            num_rows,
+            num_columns,
+            stride: num_columns + 1,
+        })
+    }
+}
+
+impl<T: Clone> GridBuf<T> {
+    /// Creates a grid where every cell has the same value.
+    #[must_use]
+    pub fn new(num_rows: usize, num_columns: usize, value: T) -> Self {
+        Self::from_vec(num_columns, vec![value; num_rows * num_columns])
+    }
+}
+
+impl<T> GridBuf<T> {
+    /// Creates a grid from its cells in row order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells is not a multiple of `num_columns`.
+    #[must_use]
+    pub fn from_vec(num_columns: usize, cells: Vec<T>) -> Self {
+        assert!(
+            num_columns > 0 && cells.len().is_multiple_of(num_columns),
+            "{} cells do not fit in rows of {num_columns}",
+            cells.len()
+        );
+        Self {
+            num_rows: cells.len() / num_columns,
+            num_columns,
+            stride: num_columns,
+            cells,
+        }
+    }
+}
+
+impl<C: Deref<Target = [T]>, T> Grid<C> {
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    #[must_use]
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.num_rows && point.column < self.num_columns
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point).then(|| &self[point])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let num_columns = self.num_columns;
+        (0..self.num_rows)
+            .flat_map(move |row| (0..num_columns).map(move |column| Point { row, column }))
+    }
+
+    /// The point next to `point` in the given direction, if it is inside the grid.
+    #[must_use]
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point
+            .step(direction)
+            .filter(|&next_point| self.contains(next_point))
+    }
+
+    /// The points from `point` (excluded) to the edge of the grid in the given direction. With a
+    /// diagonal direction, this walks along a diagonal.
+    pub fn ray(&self, point: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        iter::successors(Some(point), move |&point| self.step(point, direction)).skip(1)
+    }
+
+    /// The up to 4 points above, below, left and right of `point`.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// The up to 8 points around `point`, including diagonally.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        let start = row * self.stride;
+        &self.cells[start..start + self.num_columns]
+    }
+
+    pub fn rows<'a>(&'a self) -> impl Iterator<Item = &'a [T]>
+    where
+        T: 'a,
+    {
+        (0..self.num_rows).map(|row| self.row(row))
+    }
+
+    pub fn column<'a>(&'a self, column: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        (0..self.num_rows).map(move |row| &self[Point { row, column }])
+    }
+
+    pub fn columns<'a>(&'a self) -> impl Iterator<Item = impl Iterator<Item = &'a T>>
+    where
+        T: 'a,
+    {
+        (0..self.num_columns).map(|column| self.column(column))
+    }
+
+    /// Creates a grid of the same size, with `f` applied to every cell.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> GridBuf<U> {
+        GridBuf::from_vec(self.num_columns, self.rows().flatten().map(f).collect())
+    }
+
+    /// Creates a grid with the rows and columns swapped.
+    #[must_use]
+    pub fn transpose(&self) -> GridBuf<T>
+    where
+        T: Clone,
+    {
+        GridBuf::from_vec(self.num_rows, self.columns().flatten().cloned().collect())
+    }
+
+    /// Copies the cells into an owned grid without padding.
+    #[must_use]
+    pub fn to_grid_buf(&self) -> GridBuf<T>
+    where
+        T: Clone,
+    {
+        self.map(T::clone)
+    }
+}
+
+impl<C: Deref<Target = [T]>, T> Index<Point> for Grid<C> {
+    type Output = T;
+
+    fn index(&self, Point { row, column }: Point) -> &Self::Output {
+        assert!(
+            column < self.num_columns,
+            "column {column} is outside a grid of {} columns",
+            self.num_columns
+        );
+        &self.cells[row * self.stride + column]
+    }
+}
+
+impl<C: DerefMut<Target = [T]>, T> IndexMut<Point> for Grid<C> {
+    fn index_mut(&mut self, Point { row, column }: Point) -> &mut Self::Output {
+        assert!(
+            column < self.num_columns,
+            "column {column} is outside a grid of {} columns",
+            self.num_columns
+        );
+        &mut self.cells[row * self.stride + column]
+    }
+}
+
+/// Writes the grid as lines of characters, one per row.
+impl<C: Deref<Target = [T]>, T: Copy + Into<char>> Display for Grid<C> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        for row in self.rows() {
+            for &cell in row {
+                write!(formatter, "{}", cell.into())?;
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef\n";
+
+    fn grid() -> GridRef<'static> {
+        GridRef::parse(
+            TEXT,
+            |byte| byte.is_ascii_lowercase(),
+            "a letter",
+            "letters",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.num_rows(), grid.num_columns()), (2, 3));
+        assert_eq!(grid[Point::new(1, 2)], b'f');
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.to_string(), TEXT);
+
+        let error = GridRef::parse("ab\nc\n", |_| true, "a letter", "letters").unwrap_err();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (2, "a row of 2 letters")
+        );
+        let error = GridRef::parse(
+            "ab\nc1\n",
+            |byte| byte.is_ascii_lowercase(),
+            "a letter",
+            "letters",
+        )
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let neighbours: Vec<_> = grid.neighbours_4(Point::new(0, 1)).collect();
+        assert_eq!(
+            neighbours,
+            [Point::new(1, 1), Point::new(0, 0), Point::new(0, 2)]
+        );
+        assert_eq!(grid.neighbours_8(Point::new(0, 1)).count(), 5);
+        assert_eq!(grid.neighbours_8(Point::new(1, 0)).count(), 3);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = grid();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"abc", b"def"]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        let ray: Vec<_> = grid.ray(Point::new(0, 0), Direction::DownRight).collect();
+        assert_eq!(ray, [Point::new(1, 1)]);
+        assert_eq!(grid.ray(Point::new(1, 2), Direction::Left).count(), 2);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let transposed = grid().transpose();
+        assert_eq!(transposed.to_string(), "ad\nbe\ncf\n");
+        assert_eq!(transposed.transpose(), grid().to_grid_buf());
+    }
+
+    #[test]
+    fn test_grid_buf() {
+        let mut grid = GridBuf::new(2, 2, 0);
+        grid[Point::new(1, 0)] = 7;
+        assert_eq!(grid.row(1), [7, 0]);
+        assert_eq!(
+            grid.map(|&cell| cell > 0).column(0).collect::<Vec<_>>(),
+            [&false, &true]
+        );
+    }
+
+    #[test]
+    #[should_panic = "column 3 is outside a grid of 3 columns"]
+    fn test_index_outside() {
+        let _ = grid()[Point::new(0, 3)];
+    }
+}
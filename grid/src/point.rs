@@ -0,0 +1,88 @@
+/// A position in a grid. Rows are counted from the top and columns from the left, both from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Point {
+    #[must_use]
+    pub const fn new(row: usize, column: usize) -> Self {
+        Self { row, column }
+    }
+
+    /// The neighbouring point in the given direction, or `None` if it would be above the first
+    /// row or left of the first column.
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let (row_offset, column_offset) = direction.offset();
+        Some(Self {
+            row: self.row.checked_add_signed(row_offset)?,
+            column: self.column.checked_add_signed(column_offset)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, column): (usize, usize)) -> Self {
+        Self { row, column }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The directions of the 4-neighbourhood.
+    pub const ORTHOGONAL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// The directions of the 8-neighbourhood.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::UpLeft,
+        Self::UpRight,
+        Self::DownLeft,
+        Self::DownRight,
+    ];
+
+    /// The change in row and column when taking a step in this direction.
+    #[must_use]
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1),
+            Self::UpLeft => (-1, -1),
+            Self::UpRight => (-1, 1),
+            Self::DownLeft => (1, -1),
+            Self::DownRight => (1, 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let point = Point::new(1, 0);
+        assert_eq!(point.step(Direction::Up), Some(Point::new(0, 0)));
+        assert_eq!(point.step(Direction::DownRight), Some(Point::new(2, 1)));
+        assert_eq!(point.step(Direction::Left), None);
+        assert_eq!(point.step(Direction::UpLeft), None);
+    }
+}